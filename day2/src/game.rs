@@ -0,0 +1,248 @@
+/// A shape, identified by its position in the game's shape list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+/// Result of a single round, from our point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Loss => "Lose",
+            Outcome::Draw => "Draw",
+            Outcome::Win => "Win",
+        }
+    }
+
+    /// Points awarded for the outcome of a round
+    pub fn score(self) -> i32 {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/// Name and points of a single shape
+#[derive(Debug, Clone)]
+pub struct ShapeDef {
+    pub name: String,
+    pub score: i32,
+}
+
+impl ShapeDef {
+    pub fn new(name: &str, score: i32) -> ShapeDef {
+        ShapeDef {
+            name: name.to_string(),
+            score,
+        }
+    }
+}
+
+/// The rules of a Rock-Paper-Scissors style game: its shapes, their scores and
+/// which shape beats which. The dominance relation must be a balanced cyclic
+/// tournament, i.e. an odd number of shapes where every shape beats exactly half
+/// of the others.
+///
+/// Shapes are encoded in the strategy guide by their position: the opponent column
+/// uses letters from 'A' upwards, and our column uses the last n letters up to 'Z'
+/// (so X, Y, Z for three shapes, V to Z for five).
+#[derive(Debug, Clone)]
+pub struct Game {
+    shapes: Vec<ShapeDef>,
+    /// beats[i][j] is true if shape i defeats shape j
+    beats: Vec<Vec<bool>>,
+}
+
+impl Game {
+    /// Build a game from its shapes and a list of (winner, loser) pairs
+    pub fn new(shapes: Vec<ShapeDef>, wins: &[(usize, usize)]) -> Result<Game, String> {
+        let n = shapes.len();
+        if n.is_multiple_of(2) {
+            return Err(format!("a cyclic game needs an odd number of shapes, got {n}"));
+        }
+        if n > 13 {
+            return Err(format!("at most 13 shapes can be encoded, got {n}"));
+        }
+
+        let mut beats = vec![vec![false; n]; n];
+        for &(winner, loser) in wins {
+            if winner >= n || loser >= n || winner == loser {
+                return Err(format!("invalid dominance pair ({winner}, {loser})"));
+            }
+            beats[winner][loser] = true;
+        }
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if beats[i][j] == beats[j][i] {
+                    return Err(format!(
+                        "exactly one of {} and {} must beat the other",
+                        shapes[i].name, shapes[j].name
+                    ));
+                }
+            }
+            let wins = beats[i].iter().filter(|&&b| b).count();
+            if wins != n / 2 {
+                return Err(format!(
+                    "{} beats {wins} shapes, but every shape must beat {}",
+                    shapes[i].name,
+                    n / 2
+                ));
+            }
+        }
+
+        Ok(Game { shapes, beats })
+    }
+
+    /// Build a game where each shape beats the (n - 1) / 2 shapes that follow it in the list, wrapping around
+    pub fn cyclic(shapes: Vec<ShapeDef>) -> Result<Game, String> {
+        let n = shapes.len();
+        let wins: Vec<(usize, usize)> = (0..n)
+            .flat_map(|i| (1..=n / 2).map(move |k| (i, (i + k) % n)))
+            .collect();
+        Game::new(shapes, &wins)
+    }
+
+    /// The classic three shape game from the puzzle
+    pub fn rock_paper_scissors() -> Game {
+        let shapes = vec![
+            ShapeDef::new("Rock", 1),
+            ShapeDef::new("Paper", 2),
+            ShapeDef::new("Scissors", 3),
+        ];
+        Game::new(shapes, &[(0, 2), (1, 0), (2, 1)]).unwrap()
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        let shapes = vec![
+            ShapeDef::new("Rock", 1),
+            ShapeDef::new("Paper", 2),
+            ShapeDef::new("Scissors", 3),
+            ShapeDef::new("Lizard", 4),
+            ShapeDef::new("Spock", 5),
+        ];
+        let wins = [
+            (0, 2), // Rock crushes Scissors
+            (0, 3), // Rock crushes Lizard
+            (1, 0), // Paper covers Rock
+            (1, 4), // Paper disproves Spock
+            (2, 1), // Scissors cuts Paper
+            (2, 3), // Scissors decapitates Lizard
+            (3, 1), // Lizard eats Paper
+            (3, 4), // Lizard poisons Spock
+            (4, 0), // Spock vaporizes Rock
+            (4, 2), // Spock smashes Scissors
+        ];
+        Game::new(shapes, &wins).unwrap()
+    }
+
+    /// The seven gesture variant, where each gesture beats the next three
+    pub fn rock_paper_scissors_7() -> Game {
+        let shapes = ["Rock", "Fire", "Scissors", "Sponge", "Paper", "Air", "Water"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ShapeDef::new(name, i as i32 + 1))
+            .collect();
+        Game::cyclic(shapes).unwrap()
+    }
+
+    /// Look up one of the built in games by name
+    pub fn by_name(name: &str) -> Result<Game, String> {
+        match name {
+            "rps" => Ok(Game::rock_paper_scissors()),
+            "rpsls" => Ok(Game::rock_paper_scissors_lizard_spock()),
+            "rps7" => Ok(Game::rock_paper_scissors_7()),
+            _ => Err(format!("unknown game '{name}' (expected rps, rpsls or rps7)")),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn shapes(&self) -> impl DoubleEndedIterator<Item = Shape> {
+        (0..self.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    /// Look up a shape by its name, ignoring case
+    pub fn shape_by_name(&self, name: &str) -> Result<Shape, String> {
+        self.shapes()
+            .find(|&s| self.name(s).eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown shape '{name}'"))
+    }
+
+    /// Points awarded for throwing 'shape'
+    pub fn score(&self, shape: Shape) -> i32 {
+        self.shapes[shape.0].score
+    }
+
+    pub fn beats(&self, winner: Shape, loser: Shape) -> bool {
+        self.beats[winner.0][loser.0]
+    }
+
+    /// Outcome (from our point of view) of throwing 'ours' against 'opponent'
+    pub fn against(&self, ours: Shape, opponent: Shape) -> Outcome {
+        if ours == opponent {
+            Outcome::Draw
+        } else if self.beats(ours, opponent) {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// The shape we need to throw against 'opponent' to get 'outcome'. When several
+    /// shapes achieve it, the highest scoring one is chosen (ties go to the earliest).
+    pub fn for_outcome(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        self.shapes()
+            .filter(|&ours| self.against(ours, opponent) == outcome)
+            .fold(None, |best: Option<Shape>, s| match best {
+                Some(b) if self.score(b) >= self.score(s) => Some(b),
+                _ => Some(s),
+            })
+            .unwrap()
+    }
+
+    /// Points for a round in which we throw 'ours' against 'opponent'
+    pub fn score_round(&self, ours: Shape, opponent: Shape) -> i32 {
+        self.score(ours) + self.against(ours, opponent).score()
+    }
+
+    /// Letter used for 'shape' in the opponent column
+    pub fn opponent_code(&self, shape: Shape) -> char {
+        (b'A' + shape.0 as u8) as char
+    }
+
+    /// Letter used for position 'index' in our column
+    pub fn response_code(&self, index: usize) -> char {
+        (b'Z' + 1 - self.len() as u8 + index as u8) as char
+    }
+
+    /// Parse an opponent column code, e.g. "A"
+    pub fn parse_opponent(&self, code: &str) -> Result<Shape, String> {
+        self.shapes()
+            .find(|&s| code.len() == 1 && code.starts_with(self.opponent_code(s)))
+            .ok_or_else(|| format!("unknown opponent shape '{code}'"))
+    }
+
+    /// Parse our column code, e.g. "X", into its position in the column alphabet
+    pub fn parse_response(&self, code: &str) -> Result<usize, String> {
+        (0..self.len())
+            .find(|&i| code.len() == 1 && code.starts_with(self.response_code(i)))
+            .ok_or_else(|| format!("unknown response '{code}'"))
+    }
+}
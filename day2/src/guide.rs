@@ -0,0 +1,104 @@
+use crate::game::{Game, Outcome, Shape};
+
+/// Position of a letter in our column of the strategy guide, e.g. X = 0 in a three shape game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response(pub usize);
+
+/// One line of the strategy guide, e.g. "A Y"
+#[derive(Debug, Clone, Copy)]
+pub struct Round {
+    pub opponent: Shape,
+    pub response: Response,
+}
+
+impl Round {
+    fn parse(game: &Game, line: &str) -> Result<Round, String> {
+        let (opponent, response) = line
+            .split_once(' ')
+            .ok_or_else(|| format!("malformed round '{line}'"))?;
+
+        Ok(Round {
+            opponent: game.parse_opponent(opponent)?,
+            response: Response(game.parse_response(response)?),
+        })
+    }
+}
+
+/// Parse every line of the strategy guide, reporting the first bad line by number
+pub fn parse_guide(game: &Game, input: &str) -> Result<Vec<Round>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Round::parse(game, line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+/// A reading of our column of the strategy guide
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Each letter names the shape we throw, indexed by letter position
+    Shapes(Vec<Shape>),
+    /// The last three letters name the outcome we need, indexed by letter position
+    Outcomes([Outcome; 3]),
+}
+
+impl Interpretation {
+    /// Part 1: the letters name our shapes in order (X = Rock, Y = Paper, Z = Scissors)
+    pub fn part1(game: &Game) -> Interpretation {
+        Interpretation::Shapes(game.shapes().collect())
+    }
+
+    /// Part 2: the last three letters are outcomes (X = Lose, Y = Draw, Z = Win)
+    pub fn part2() -> Interpretation {
+        Interpretation::Outcomes(Outcome::ALL)
+    }
+
+    /// The shape we throw and the outcome we get for 'round' under this interpretation
+    pub fn play(&self, game: &Game, round: &Round) -> Result<(Shape, Outcome), String> {
+        match self {
+            Interpretation::Shapes(shapes) => {
+                let ours = shapes[round.response.0];
+                Ok((ours, game.against(ours, round.opponent)))
+            }
+            Interpretation::Outcomes(outcomes) => {
+                let outcome = (round.response.0 + outcomes.len())
+                    .checked_sub(game.len())
+                    .map(|i| outcomes[i])
+                    .ok_or_else(|| {
+                        format!(
+                            "'{}' does not encode an outcome",
+                            game.response_code(round.response.0)
+                        )
+                    })?;
+                Ok((game.for_outcome(round.opponent, outcome), outcome))
+            }
+        }
+    }
+
+    /// Points for 'round' under this interpretation
+    pub fn score(&self, game: &Game, round: &Round) -> Result<i32, String> {
+        let (ours, outcome) = self.play(game, round)?;
+        Ok(game.score(ours) + outcome.score())
+    }
+
+    /// Total points for the whole strategy guide
+    pub fn total(&self, game: &Game, rounds: &[Round]) -> Result<i32, String> {
+        rounds.iter().map(|round| self.score(game, round)).sum()
+    }
+
+    /// Human readable mapping, e.g. "X=Rock Y=Paper Z=Scissors"
+    pub fn describe(&self, game: &Game) -> String {
+        let names: Vec<&str> = match self {
+            Interpretation::Shapes(shapes) => shapes.iter().map(|&s| game.name(s)).collect(),
+            Interpretation::Outcomes(outcomes) => outcomes.iter().map(|o| o.name()).collect(),
+        };
+        let first_letter = game.len() - names.len();
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| format!("{}={name}", game.response_code(first_letter + i)))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
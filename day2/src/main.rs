@@ -1,44 +1,128 @@
-fn main() {
-    // Part 1
-    let score: i32 = include_str!("day2.txt")
-        .lines()
-        .map(|round| {
-            match round {
-                "A X" => 1 + 3, // draw
-                "B X" => 1 + 0, // loss
-                "C X" => 1 + 6, // win
-                "A Y" => 2 + 6, // win
-                "B Y" => 2 + 3, // draw
-                "C Y" => 2 + 0, // loss
-                "A Z" => 3 + 0, // loss
-                "B Z" => 3 + 6, // win
-                "C Z" => 3 + 3, // draw
-                &_ => 0,
+mod game;
+mod guide;
+mod optimise;
+mod report;
+mod simulate;
+
+use game::Game;
+use guide::{parse_guide, Interpretation};
+
+/// What the binary should do with the strategy guide
+enum Mode {
+    /// Score the guide under the part 1 and part 2 interpretations
+    Parts,
+    /// Rank every possible interpretation of our column
+    Optimise,
+    /// Play matches between strategies and report score distributions
+    Simulate,
+    /// List every round under both interpretations, with aggregate totals
+    Report,
+}
+
+/// Parse a numeric option value
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for {arg}"))
+}
+
+/// Command line options:
+/// `day2 [optimise|simulate|report] [--game rps|rpsls|rps7] [--input PATH]
+///       [--players a,b,..] [--rounds N] [--matches N] [--seed N]`
+struct Options {
+    mode: Mode,
+    game: Game,
+    input: String,
+    simulation: simulate::Config,
+}
+
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            mode: Mode::Parts,
+            game: Game::rock_paper_scissors(),
+            input: include_str!("day2.txt").to_string(),
+            simulation: simulate::Config::default(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("missing value for {arg}"));
+            match arg.as_str() {
+                "optimise" => options.mode = Mode::Optimise,
+                "simulate" => options.mode = Mode::Simulate,
+                "report" => options.mode = Mode::Report,
+                "--game" => options.game = Game::by_name(&value()?)?,
+                "--input" => {
+                    let path = value()?;
+                    options.input = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("could not read {path}: {e}"))?;
+                }
+                "--players" => {
+                    options.simulation.players = value()?.split(',').map(String::from).collect()
+                }
+                "--rounds" => options.simulation.rounds = Some(parse_number(&arg, &value()?)?),
+                "--matches" => options.simulation.matches = parse_number(&arg, &value()?)?,
+                "--seed" => options.simulation.seed = parse_number(&arg, &value()?)?,
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn main() -> Result<(), String> {
+    let Options {
+        mode,
+        game,
+        input,
+        simulation,
+    } = Options::from_args(std::env::args().skip(1))?;
+    let rounds = parse_guide(&game, &input)?;
+
+    match mode {
+        Mode::Parts => {
+            // Part 1
+            // X = Rock, Y = Paper, Z = Scissors
+            let score = Interpretation::part1(&game).total(&game, &rounds)?;
+
+            println!("{score:?}");
+
+            // Part 2
+            // X = Lose, Y = Draw, Z = Win
+            let score = Interpretation::part2().total(&game, &rounds)?;
+
+            println!("{score:?}");
+        }
+        Mode::Optimise => {
+            let ranking = optimise::rank(&game, &rounds, optimise::shape_interpretations(&game))?;
+            optimise::print_ranking(&game, "Letters as shapes", &ranking);
+
+            println!();
+            match optimise::rank(&game, &rounds, optimise::outcome_interpretations()) {
+                Ok(ranking) => optimise::print_ranking(&game, "Letters as outcomes", &ranking),
+                Err(e) => println!("Letters as outcomes: not applicable ({e})"),
             }
-        })
-        .sum();
-
-    println!("{score:?}");
-
-    // Part 2
-    // X = Lose, Y = Draw, Z = Win
-    let score: i32 = include_str!("day2.txt")
-        .lines()
-        .map(|round| {
-            match round {
-                "A X" => 3 + 0, // lose with Z
-                "B X" => 1 + 0, // lose with X
-                "C X" => 2 + 0, // lose with Y
-                "A Y" => 1 + 3, // draw with X
-                "B Y" => 2 + 3, // draw with Y
-                "C Y" => 3 + 3, // draw with Z
-                "A Z" => 2 + 6, // win with Y
-                "B Z" => 3 + 6, // win with Z
-                "C Z" => 1 + 6, // win with X
-                &_ => 0,
+        }
+        Mode::Simulate => {
+            let pairings = simulate::round_robin(&game, &rounds, &simulation)?;
+            simulate::print_pairings(&pairings);
+        }
+        Mode::Report => {
+            let interpretations = [
+                ("Part 1", Interpretation::part1(&game)),
+                ("Part 2", Interpretation::part2()),
+            ];
+            let mut columns = vec![];
+            for (label, interpretation) in &interpretations {
+                match report::Column::new(&game, &rounds, label, interpretation) {
+                    Ok(column) => columns.push(column),
+                    Err(e) => println!("{label}: not applicable ({e})"),
+                }
             }
-        })
-        .sum();
+            report::print_report(&game, &rounds, &columns);
+        }
+    }
 
-    println!("{score:?}");
+    Ok(())
 }
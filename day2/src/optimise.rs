@@ -0,0 +1,89 @@
+use crate::game::{Game, Outcome};
+use crate::guide::{Interpretation, Response, Round};
+
+/// Every ordering of 'items' (n! of them), in lexicographic order of positions
+fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![vec![]];
+    }
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+            permutations(&rest).into_iter().map(move |mut perm| {
+                perm.insert(0, first.clone());
+                perm
+            })
+        })
+        .collect()
+}
+
+/// Every bijection of our column's letters to shapes
+pub fn shape_interpretations(game: &Game) -> Vec<Interpretation> {
+    permutations(&game.shapes().collect::<Vec<_>>())
+        .into_iter()
+        .map(Interpretation::Shapes)
+        .collect()
+}
+
+/// Every bijection of the last three letters of our column to outcomes
+pub fn outcome_interpretations() -> Vec<Interpretation> {
+    permutations(&Outcome::ALL)
+        .into_iter()
+        .map(|perm| Interpretation::Outcomes([perm[0], perm[1], perm[2]]))
+        .collect()
+}
+
+/// Score every interpretation against the guide and return them best first.
+/// Rounds are tallied by (opponent, response) once, so each interpretation costs
+/// n² rather than one pass over the guide.
+pub fn rank(
+    game: &Game,
+    rounds: &[Round],
+    interpretations: Vec<Interpretation>,
+) -> Result<Vec<(Interpretation, i32)>, String> {
+    let n = game.len();
+    let mut tally = vec![vec![0; n]; n];
+    for round in rounds {
+        tally[round.opponent.0][round.response.0] += 1;
+    }
+
+    let mut ranking = interpretations
+        .into_iter()
+        .map(|interpretation| {
+            let mut total = 0;
+            for opponent in game.shapes() {
+                for (response, &count) in tally[opponent.0].iter().enumerate() {
+                    if count > 0 {
+                        let round = Round {
+                            opponent,
+                            response: Response(response),
+                        };
+                        total += count * interpretation.score(game, &round)?;
+                    }
+                }
+            }
+            Ok((interpretation, total))
+        })
+        .collect::<Result<Vec<(Interpretation, i32)>, String>>()?;
+
+    // Stable sort, so ties keep their enumeration order
+    ranking.sort_by_key(|&(_, total)| std::cmp::Reverse(total));
+    Ok(ranking)
+}
+
+/// Print the best and worst interpretations followed by the full ranked table
+pub fn print_ranking(game: &Game, title: &str, ranking: &[(Interpretation, i32)]) {
+    let (Some((best, best_total)), Some((worst, worst_total))) = (ranking.first(), ranking.last())
+    else {
+        return;
+    };
+
+    println!("{title}");
+    println!("  Best:  {best_total} ({})", best.describe(game));
+    println!("  Worst: {worst_total} ({})", worst.describe(game));
+    println!("  Spread: {}", best_total - worst_total);
+    for (rank, (interpretation, total)) in ranking.iter().enumerate() {
+        println!("  {:>4}. {total:>8}  {}", rank + 1, interpretation.describe(game));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::game::{Game, Outcome, Shape};
+use crate::guide::{Interpretation, Round};
+
+/// Every round of the guide played under one interpretation
+pub struct Column {
+    pub label: String,
+    pub description: String,
+    pub plays: Vec<(Shape, Outcome)>,
+}
+
+impl Column {
+    pub fn new(
+        game: &Game,
+        rounds: &[Round],
+        label: &str,
+        interpretation: &Interpretation,
+    ) -> Result<Column, String> {
+        let plays = rounds
+            .iter()
+            .map(|round| interpretation.play(game, round))
+            .collect::<Result<Vec<(Shape, Outcome)>, String>>()?;
+
+        Ok(Column {
+            label: label.to_string(),
+            description: interpretation.describe(game),
+            plays,
+        })
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.plays.iter().filter(|(_, o)| *o == outcome).count()
+    }
+
+    fn shape_points(&self, game: &Game) -> i32 {
+        self.plays.iter().map(|&(s, _)| game.score(s)).sum()
+    }
+
+    fn outcome_points(&self) -> i32 {
+        self.plays.iter().map(|(_, o)| o.score()).sum()
+    }
+}
+
+/// Print one line per round with what each interpretation throws and scores, then the totals
+pub fn print_report(game: &Game, rounds: &[Round], columns: &[Column]) {
+    print!("{:>5}  {:<5}  {:<10}", "Round", "Guide", "Opponent");
+    for column in columns {
+        print!(" | {:<10} {:<7} {:>6}", format!("{}:", column.label), "", "");
+    }
+    println!();
+    print!("{:>5}  {:<5}  {:<10}", "", "", "");
+    for _ in columns {
+        print!(" | {:<10} {:<7} {:>6}", "Ours", "Outcome", "Points");
+    }
+    println!();
+
+    for (i, round) in rounds.iter().enumerate() {
+        print!(
+            "{:>5}  {} {}    {:<10}",
+            i + 1,
+            game.opponent_code(round.opponent),
+            game.response_code(round.response.0),
+            game.name(round.opponent)
+        );
+        for column in columns {
+            let (ours, outcome) = column.plays[i];
+            print!(
+                " | {:<10} {:<7} {:>6}",
+                game.name(ours),
+                outcome.name(),
+                game.score(ours) + outcome.score()
+            );
+        }
+        println!();
+    }
+
+    for column in columns {
+        let shape_points = column.shape_points(game);
+        let outcome_points = column.outcome_points();
+
+        println!();
+        println!("{} ({})", column.label, column.description);
+        println!(
+            "  Wins/Draws/Losses:    {}/{}/{}",
+            column.count(Outcome::Win),
+            column.count(Outcome::Draw),
+            column.count(Outcome::Loss)
+        );
+        println!("  Points from shapes:   {shape_points}");
+        println!("  Points from outcomes: {outcome_points}");
+        println!("  Total:                {}", shape_points + outcome_points);
+    }
+}
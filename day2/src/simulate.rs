@@ -0,0 +1,353 @@
+use crate::game::{Game, Outcome, Shape};
+use crate::guide::{Interpretation, Round};
+
+/// Small seedable pseudo random generator (SplitMix64), so matches are reproducible
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// A player that picks a shape each round, and may learn from previous rounds
+pub trait Strategy {
+    /// Shape to throw in the next round
+    fn choose(&mut self, game: &Game) -> Shape;
+
+    /// Called after every round with what we threw and what the opponent threw
+    fn observe(&mut self, _game: &Game, _ours: Shape, _theirs: Shape) {}
+}
+
+/// Always throws the same shape
+pub struct Fixed(pub Shape);
+
+impl Strategy for Fixed {
+    fn choose(&mut self, _game: &Game) -> Shape {
+        self.0
+    }
+}
+
+/// Throws a uniformly random shape
+pub struct Random(pub Rng);
+
+impl Strategy for Random {
+    fn choose(&mut self, game: &Game) -> Shape {
+        Shape(self.0.below(game.len()))
+    }
+}
+
+/// Counts the opponent's throws and plays whatever beats their most frequent shape
+pub struct FrequencyCounter {
+    counts: Vec<usize>,
+}
+
+impl FrequencyCounter {
+    pub fn new(game: &Game) -> FrequencyCounter {
+        FrequencyCounter {
+            counts: vec![0; game.len()],
+        }
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn choose(&mut self, game: &Game) -> Shape {
+        // max_by_key returns the last maximum, so reverse to break ties towards the first shape
+        let predicted = game
+            .shapes()
+            .rev()
+            .max_by_key(|s| self.counts[s.0])
+            .unwrap();
+        game.for_outcome(predicted, Outcome::Win)
+    }
+
+    fn observe(&mut self, _game: &Game, _ours: Shape, theirs: Shape) {
+        self.counts[theirs.0] += 1;
+    }
+}
+
+/// Keeps its shape after a win, otherwise switches to whatever beats the opponent's last throw
+pub struct WinStayLoseShift {
+    next: Shape,
+}
+
+impl WinStayLoseShift {
+    pub fn new() -> WinStayLoseShift {
+        WinStayLoseShift { next: Shape(0) }
+    }
+}
+
+impl Strategy for WinStayLoseShift {
+    fn choose(&mut self, _game: &Game) -> Shape {
+        self.next
+    }
+
+    fn observe(&mut self, game: &Game, ours: Shape, theirs: Shape) {
+        if game.against(ours, theirs) != Outcome::Win {
+            self.next = game.for_outcome(theirs, Outcome::Win);
+        }
+    }
+}
+
+/// Plays the strategy guide line by line (wrapping around), assuming the opponent
+/// throws what the guide predicts
+pub struct FollowGuide {
+    shapes: Vec<Shape>,
+    next: usize,
+}
+
+impl FollowGuide {
+    pub fn new(
+        game: &Game,
+        rounds: &[Round],
+        interpretation: &Interpretation,
+    ) -> Result<FollowGuide, String> {
+        if rounds.is_empty() {
+            return Err("cannot follow an empty strategy guide".to_string());
+        }
+        let shapes = rounds
+            .iter()
+            .map(|round| Ok(interpretation.play(game, round)?.0))
+            .collect::<Result<Vec<Shape>, String>>()?;
+
+        Ok(FollowGuide { shapes, next: 0 })
+    }
+}
+
+impl Strategy for FollowGuide {
+    fn choose(&mut self, _game: &Game) -> Shape {
+        let shape = self.shapes[self.next];
+        self.next = (self.next + 1) % self.shapes.len();
+        shape
+    }
+}
+
+/// A named recipe for building a fresh strategy for every match
+#[derive(Debug, Clone)]
+pub enum StrategySpec {
+    Fixed(Shape),
+    Random,
+    FrequencyCounter,
+    WinStayLoseShift,
+    Guide1,
+    Guide2,
+}
+
+impl StrategySpec {
+    /// Parse "fixed[:SHAPE]", "random", "frequency", "wsls", "guide1" or "guide2"
+    pub fn parse(game: &Game, spec: &str) -> Result<StrategySpec, String> {
+        match spec.split_once(':') {
+            Some(("fixed", shape)) => Ok(StrategySpec::Fixed(game.shape_by_name(shape)?)),
+            Some(_) => Err(format!("unknown strategy '{spec}'")),
+            None => match spec {
+                "fixed" => Ok(StrategySpec::Fixed(Shape(0))),
+                "random" => Ok(StrategySpec::Random),
+                "frequency" => Ok(StrategySpec::FrequencyCounter),
+                "wsls" => Ok(StrategySpec::WinStayLoseShift),
+                "guide1" => Ok(StrategySpec::Guide1),
+                "guide2" => Ok(StrategySpec::Guide2),
+                _ => Err(format!("unknown strategy '{spec}'")),
+            },
+        }
+    }
+
+    pub fn name(&self, game: &Game) -> String {
+        match self {
+            StrategySpec::Fixed(shape) => format!("fixed:{}", game.name(*shape)),
+            StrategySpec::Random => "random".to_string(),
+            StrategySpec::FrequencyCounter => "frequency".to_string(),
+            StrategySpec::WinStayLoseShift => "wsls".to_string(),
+            StrategySpec::Guide1 => "guide1".to_string(),
+            StrategySpec::Guide2 => "guide2".to_string(),
+        }
+    }
+
+    pub fn build(
+        &self,
+        game: &Game,
+        rounds: &[Round],
+        seed: u64,
+    ) -> Result<Box<dyn Strategy>, String> {
+        Ok(match self {
+            StrategySpec::Fixed(shape) => Box::new(Fixed(*shape)),
+            StrategySpec::Random => Box::new(Random(Rng::new(seed))),
+            StrategySpec::FrequencyCounter => Box::new(FrequencyCounter::new(game)),
+            StrategySpec::WinStayLoseShift => Box::new(WinStayLoseShift::new()),
+            StrategySpec::Guide1 => Box::new(FollowGuide::new(
+                game,
+                rounds,
+                &Interpretation::part1(game),
+            )?),
+            StrategySpec::Guide2 => {
+                Box::new(FollowGuide::new(game, rounds, &Interpretation::part2())?)
+            }
+        })
+    }
+}
+
+/// Play 'rounds' rounds between two strategies, returning each side's total score
+pub fn play_match(
+    game: &Game,
+    first: &mut dyn Strategy,
+    second: &mut dyn Strategy,
+    rounds: usize,
+) -> (i32, i32) {
+    let mut totals = (0, 0);
+    for _ in 0..rounds {
+        let a = first.choose(game);
+        let b = second.choose(game);
+        totals.0 += game.score_round(a, b);
+        totals.1 += game.score_round(b, a);
+        first.observe(game, a, b);
+        second.observe(game, b, a);
+    }
+    totals
+}
+
+/// Distribution of one side's match totals
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Summary {
+    fn of(totals: &[i32]) -> Summary {
+        let n = totals.len() as f64;
+        let mean = totals.iter().map(|&t| t as f64).sum::<f64>() / n;
+        let variance = totals.iter().map(|&t| (t as f64 - mean).powi(2)).sum::<f64>() / n;
+        Summary {
+            mean,
+            std_dev: variance.sqrt(),
+            min: *totals.iter().min().unwrap(),
+            max: *totals.iter().max().unwrap(),
+        }
+    }
+}
+
+/// Result of a series of matches between two strategies
+#[derive(Debug, Clone)]
+pub struct Pairing {
+    pub first: String,
+    pub second: String,
+    pub first_scores: Summary,
+    pub second_scores: Summary,
+    /// Matches won, drawn and lost by the first strategy
+    pub record: (usize, usize, usize),
+}
+
+/// Settings for a simulation run
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub players: Vec<String>,
+    pub rounds: Option<usize>,
+    pub matches: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            players: ["guide1", "guide2", "fixed", "random", "frequency", "wsls"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            rounds: None,
+            matches: 100,
+            seed: 1,
+        }
+    }
+}
+
+/// Play a series of matches between two strategies. Each match gets fresh strategies
+/// with their own seeds, so only random players vary from match to match.
+pub fn play_series(
+    game: &Game,
+    guide: &[Round],
+    first: &StrategySpec,
+    second: &StrategySpec,
+    config: &Config,
+) -> Result<Pairing, String> {
+    if config.matches == 0 {
+        return Err("at least one match is needed".to_string());
+    }
+    let rounds = config.rounds.unwrap_or(guide.len());
+
+    let mut results = Vec::with_capacity(config.matches);
+    for m in 0..config.matches as u64 {
+        let seed = config.seed.wrapping_add(m.wrapping_mul(2));
+        let mut a = first.build(game, guide, seed)?;
+        let mut b = second.build(game, guide, seed.wrapping_add(1))?;
+        results.push(play_match(game, a.as_mut(), b.as_mut(), rounds));
+    }
+
+    let firsts: Vec<i32> = results.iter().map(|r| r.0).collect();
+    let seconds: Vec<i32> = results.iter().map(|r| r.1).collect();
+    let record = results.iter().fold((0, 0, 0), |(w, d, l), (a, b)| match a.cmp(b) {
+        std::cmp::Ordering::Greater => (w + 1, d, l),
+        std::cmp::Ordering::Equal => (w, d + 1, l),
+        std::cmp::Ordering::Less => (w, d, l + 1),
+    });
+
+    Ok(Pairing {
+        first: first.name(game),
+        second: second.name(game),
+        first_scores: Summary::of(&firsts),
+        second_scores: Summary::of(&seconds),
+        record,
+    })
+}
+
+/// Play every pair of configured strategies against each other
+pub fn round_robin(game: &Game, guide: &[Round], config: &Config) -> Result<Vec<Pairing>, String> {
+    let specs = config
+        .players
+        .iter()
+        .map(|p| StrategySpec::parse(game, p))
+        .collect::<Result<Vec<StrategySpec>, String>>()?;
+
+    let mut pairings = vec![];
+    for i in 0..specs.len() {
+        for j in (i + 1)..specs.len() {
+            pairings.push(play_series(game, guide, &specs[i], &specs[j], config)?);
+        }
+    }
+    Ok(pairings)
+}
+
+pub fn print_pairings(pairings: &[Pairing]) {
+    println!(
+        "{:<16} {:<16} {:>10} {:>8} {:>17} {:>10} {:>8} {:>17} {:>11}",
+        "first", "second", "mean", "sd", "min..max", "mean", "sd", "min..max", "W/D/L"
+    );
+    for p in pairings {
+        let (w, d, l) = p.record;
+        println!(
+            "{:<16} {:<16} {:>10.1} {:>8.1} {:>17} {:>10.1} {:>8.1} {:>17} {:>11}",
+            p.first,
+            p.second,
+            p.first_scores.mean,
+            p.first_scores.std_dev,
+            format!("{}..{}", p.first_scores.min, p.first_scores.max),
+            p.second_scores.mean,
+            p.second_scores.std_dev,
+            format!("{}..{}", p.second_scores.min, p.second_scores.max),
+            format!("{w}/{d}/{l}"),
+        );
+    }
+}
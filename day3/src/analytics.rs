@@ -0,0 +1,221 @@
+use crate::items::ItemSet;
+use crate::rucksack::{common_items, compartments, groups, Layout};
+
+/// Statistics for one item type across the whole rucksack list
+#[derive(Debug, Clone, Default)]
+pub struct ItemStats {
+    pub item: char,
+    pub priority: u32,
+    /// Number of this item held in each compartment position, over all rucksacks
+    pub per_compartment: Vec<u32>,
+    /// Number of rucksacks holding at least one
+    pub rucksacks: u32,
+    /// Number of rucksacks where it is in every compartment
+    pub duplicates: u32,
+    /// Number of groups where it is carried by every elf
+    pub badges: u32,
+    /// Priority it contributes to the part 1 total
+    pub part1_priority: u32,
+    /// Priority it contributes to the part 2 total
+    pub part2_priority: u32,
+}
+
+impl ItemStats {
+    pub fn count(&self) -> u32 {
+        self.per_compartment.iter().sum()
+    }
+}
+
+/// Item statistics and co-occurrence counts for a rucksack list
+#[derive(Debug, Clone)]
+pub struct Analytics {
+    pub compartments: usize,
+    /// Indexed by priority - 1, so 'a' first and 'Z' last
+    pub items: Vec<ItemStats>,
+    /// cooccurrence[i][j] is the number of groups holding both item type i + 1 and j + 1
+    pub cooccurrence: Vec<Vec<u32>>,
+}
+
+pub fn analyse(rucksacks: &[&str], layout: Layout) -> Result<Analytics, String> {
+    let mut items: Vec<ItemStats> = (1..=52)
+        .map(|p| ItemStats {
+            item: ItemSet::item(p),
+            priority: p,
+            per_compartment: vec![0; layout.compartments],
+            ..ItemStats::default()
+        })
+        .collect();
+
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let parts = compartments(rucksack, layout.compartments)
+            .map_err(|e| format!("line {}: {e}", i + 1))?;
+        let mut sets = vec![];
+        for (c, part) in parts.iter().enumerate() {
+            for item in part.chars() {
+                let p = ItemSet::priority(item)
+                    .ok_or_else(|| format!("line {}: '{item}' is not an item type", i + 1))?;
+                items[p as usize - 1].per_compartment[c] += 1;
+            }
+            sets.push(ItemSet::parse(part)?);
+        }
+
+        for p in sets
+            .iter()
+            .fold(ItemSet::EMPTY, |acc, &s| acc.union(s))
+            .priorities()
+        {
+            items[p as usize - 1].rucksacks += 1;
+        }
+        let shared = common_items(&sets);
+        for p in shared.priorities() {
+            items[p as usize - 1].duplicates += 1;
+        }
+        if let Some(p) = shared.priorities().next() {
+            items[p as usize - 1].part1_priority += p;
+        }
+    }
+
+    let mut cooccurrence = vec![vec![0; 52]; 52];
+    for group in groups(rucksacks, layout.group_size)? {
+        let sets = group
+            .iter()
+            .map(|rucksack| ItemSet::parse(rucksack))
+            .collect::<Result<Vec<ItemSet>, String>>()?;
+
+        let badges = common_items(&sets);
+        for p in badges.priorities() {
+            items[p as usize - 1].badges += 1;
+        }
+        if let Some(p) = badges.priorities().next() {
+            items[p as usize - 1].part2_priority += p;
+        }
+
+        let present = sets.iter().fold(ItemSet::EMPTY, |acc, &s| acc.union(s));
+        for a in present.priorities() {
+            for b in present.priorities() {
+                cooccurrence[a as usize - 1][b as usize - 1] += 1;
+            }
+        }
+    }
+
+    Ok(Analytics {
+        compartments: layout.compartments,
+        items,
+        cooccurrence,
+    })
+}
+
+impl Analytics {
+    /// Item types ordered by how many rucksacks have them in every compartment, most first
+    pub fn top_duplicates(&self) -> Vec<&ItemStats> {
+        let mut ranked: Vec<&ItemStats> = self.items.iter().filter(|s| s.duplicates > 0).collect();
+        ranked.sort_by_key(|s| std::cmp::Reverse(s.duplicates));
+        ranked
+    }
+
+    /// Distinct item type pairs ordered by how many groups hold both, most first
+    pub fn top_pairs(&self) -> Vec<(char, char, u32)> {
+        let mut pairs = vec![];
+        for i in 0..52 {
+            for j in (i + 1)..52 {
+                if self.cooccurrence[i][j] > 0 {
+                    pairs.push((
+                        self.items[i].item,
+                        self.items[j].item,
+                        self.cooccurrence[i][j],
+                    ));
+                }
+            }
+        }
+        pairs.sort_by_key(|&(_, _, n)| std::cmp::Reverse(n));
+        pairs
+    }
+
+    /// Per item statistics as CSV, one row per item type
+    pub fn items_csv(&self) -> String {
+        let mut header = vec![
+            "item".to_string(),
+            "priority".to_string(),
+            "count".to_string(),
+        ];
+        header.extend((1..=self.compartments).map(|c| format!("compartment_{c}")));
+        header.extend(
+            [
+                "rucksacks",
+                "duplicates",
+                "badges",
+                "part1_priority",
+                "part2_priority",
+            ]
+            .iter()
+            .map(|s| s.to_string()),
+        );
+
+        let mut csv = header.join(",") + "\n";
+        for s in &self.items {
+            let mut row = vec![
+                s.item.to_string(),
+                s.priority.to_string(),
+                s.count().to_string(),
+            ];
+            row.extend(s.per_compartment.iter().map(|n| n.to_string()));
+            row.extend(
+                [
+                    s.rucksacks,
+                    s.duplicates,
+                    s.badges,
+                    s.part1_priority,
+                    s.part2_priority,
+                ]
+                .iter()
+                .map(|n| n.to_string()),
+            );
+            csv += &(row.join(",") + "\n");
+        }
+        csv
+    }
+
+    /// Group co-occurrence matrix as CSV, with item types as row and column labels
+    pub fn cooccurrence_csv(&self) -> String {
+        let labels: Vec<String> = self.items.iter().map(|s| s.item.to_string()).collect();
+        let mut csv = format!("item,{}\n", labels.join(","));
+        for (label, row) in labels.iter().zip(&self.cooccurrence) {
+            let cells: Vec<String> = row.iter().map(|n| n.to_string()).collect();
+            csv += &format!("{label},{}\n", cells.join(","));
+        }
+        csv
+    }
+
+    /// Human readable summary
+    pub fn print_summary(&self, top: usize) {
+        println!(
+            "{:<4} {:>8} {:>6} {:>9} {:>10} {:>6} {:>6} {:>6}",
+            "Item", "Priority", "Count", "Rucksacks", "Duplicates", "Badges", "Part 1", "Part 2"
+        );
+        for s in self.items.iter().filter(|s| s.count() > 0) {
+            println!(
+                "{:<4} {:>8} {:>6} {:>9} {:>10} {:>6} {:>6} {:>6}",
+                s.item,
+                s.priority,
+                s.count(),
+                s.rucksacks,
+                s.duplicates,
+                s.badges,
+                s.part1_priority,
+                s.part2_priority
+            );
+        }
+
+        println!();
+        println!("Most frequent duplicates:");
+        for s in self.top_duplicates().iter().take(top) {
+            println!("  {} in {} rucksacks", s.item, s.duplicates);
+        }
+
+        println!();
+        println!("Most frequent pairs within a group:");
+        for (a, b, n) in self.top_pairs().iter().take(top) {
+            println!("  {a} & {b} in {n} groups");
+        }
+    }
+}
@@ -0,0 +1,96 @@
+/// A set of item types, stored as a bitmask where bit 'p' is set if the item with priority 'p' is present
+/// (a-z have priorities 1 to 26, A-Z have 27 to 52)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    /// Every item type, bits 1 to 52
+    pub const ALL: ItemSet = ItemSet(((1 << 53) - 1) & !1);
+
+    /// Priority of an item type, or None if the char is not an item
+    pub fn priority(item: char) -> Option<u32> {
+        match item {
+            'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+            _ => None,
+        }
+    }
+
+    /// Item type with the given priority (1 to 52)
+    pub fn item(priority: u32) -> char {
+        match priority {
+            1..=26 => (b'a' + (priority - 1) as u8) as char,
+            27..=52 => (b'A' + (priority - 27) as u8) as char,
+            _ => panic!("no item has priority {priority}"),
+        }
+    }
+
+    /// Build the set of item types in 'items', e.g. "vJrwpWtwJgWr"
+    pub fn parse(items: &str) -> Result<ItemSet, String> {
+        items.chars().try_fold(ItemSet::EMPTY, |set, item| {
+            ItemSet::priority(item)
+                .map(|p| set.with_priority(p))
+                .ok_or_else(|| format!("'{item}' is not an item type"))
+        })
+    }
+
+    fn with_priority(self, priority: u32) -> ItemSet {
+        ItemSet(self.0 | 1 << priority)
+    }
+
+    /// Add an item type to the set, ignoring chars that are not items
+    pub fn insert(&mut self, item: char) {
+        if let Some(p) = ItemSet::priority(item) {
+            *self = self.with_priority(p);
+        }
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        ItemSet::priority(item).is_some_and(|p| self.0 & 1 << p != 0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Item type with the lowest priority in the set
+    pub fn first(self) -> Option<char> {
+        self.priorities().next().map(ItemSet::item)
+    }
+
+    /// Priorities of the item types in the set, lowest first
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let p = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(p)
+        })
+    }
+
+    /// Item types in the set, lowest priority first
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        self.priorities().map(ItemSet::item)
+    }
+
+    /// Sum of the priorities of every item type in the set
+    pub fn priority_sum(self) -> u32 {
+        self.priorities().sum()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod analytics;
+pub mod items;
+pub mod plan;
+pub mod rucksack;
+pub mod validate;
@@ -1,69 +1,144 @@
-use std::{
-    collections::{HashMap, HashSet},
-    iter::zip,
-};
+use day3::rucksack::{self, Layout};
+use day3::{analytics, plan, validate};
 
-fn find_common_items(left: Vec<char>, right: Vec<char>) -> Vec<char> {
-    let pocket1: HashSet<char> = left.into_iter().map(|x| x).collect();
-    let pocket2: HashSet<char> = right.into_iter().map(|x| x).collect();
+/// What the binary should do with the rucksack list
+enum Mode {
+    /// Sum the compartment and badge priorities
+    Parts,
+    /// Report every rucksack and group that does not share exactly one item type
+    Validate,
+    /// Plan the fewest item moves so no item type is in more than one compartment.
+    /// '--across-groups' also lets a rucksack that cannot be fixed alone exchange one
+    /// item within its group, chosen greedily, so that part is not guaranteed minimal.
+    Plan,
+    /// Item frequency, duplicate and co-occurrence statistics
+    Analytics,
+}
+
+/// Table to export as CSV in analytics mode
+enum Csv {
+    Items,
+    Cooccurrence,
+}
+
+/// Parse a numeric option value
+fn parse_number(arg: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for {arg}"))
+}
 
-    (*pocket1
-        .intersection(&pocket2)
-        .into_iter()
-        .map(|x| *x)
-        .collect::<Vec<char>>())
-    .to_vec()
+/// Command line options:
+/// `day3 [validate|plan|analytics] [--across-groups] [--csv items|cooccurrence]
+///       [--compartments N] [--group-size N] [--input PATH]`
+struct Options {
+    mode: Mode,
+    across_groups: bool,
+    csv: Option<Csv>,
+    layout: Layout,
+    input: String,
 }
 
-fn main() {
-    // Part 1
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            mode: Mode::Parts,
+            across_groups: false,
+            csv: None,
+            layout: Layout::default(),
+            input: include_str!("day3.txt").to_string(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "validate" => options.mode = Mode::Validate,
+                "plan" => options.mode = Mode::Plan,
+                "analytics" => options.mode = Mode::Analytics,
+                "--across-groups" => options.across_groups = true,
+                "--csv" => {
+                    options.csv = match value()?.as_str() {
+                        "items" => Some(Csv::Items),
+                        "cooccurrence" => Some(Csv::Cooccurrence),
+                        table => return Err(format!("unknown CSV table '{table}'")),
+                    }
+                }
+                "--compartments" => options.layout.compartments = parse_number(&arg, &value()?)?,
+                "--group-size" => options.layout.group_size = parse_number(&arg, &value()?)?,
+                "--input" => {
+                    let path = value()?;
+                    options.input = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("could not read {path}: {e}"))?;
+                }
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
+    }
+}
 
-    // Construct priority value lookup hashmap
-    let alphabet = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars();
-    let prior: Vec<i32> = (1..53).collect();
-    let lookup_pairs = zip(alphabet, prior);
+fn main() -> Result<(), String> {
+    let Options {
+        mode,
+        across_groups,
+        csv,
+        layout,
+        input,
+    } = Options::from_args(std::env::args().skip(1))?;
+    let rucksacks: Vec<&str> = input.lines().collect();
 
-    let priority_lookup: HashMap<char, i32> = HashMap::from_iter(lookup_pairs);
+    match mode {
+        Mode::Parts => {
+            // Part 1
+            let priority = rucksack::compartment_priorities(&rucksacks, layout)?;
 
-    // Main logic
-    let priority: i32 = include_str!("day3.txt")
-        .lines()
-        .map(|group| {
-            let group_vec: Vec<char> = group.chars().collect();
-            let (head, tail) = group_vec.split_at(group_vec.len() / 2);
-            let l = head.to_owned();
-            let r = tail.to_owned();
-            (l, r)
-        })
-        .map(|pair: (Vec<char>, Vec<char>)| {
-            find_common_items(pair.0, pair.1).first().unwrap().clone()
-        })
-        .map(|ch| priority_lookup.get(&ch).unwrap())
-        .sum();
+            println!("Part 1: {:#?}", priority);
 
-    println!("Part 1: {:#?}", priority);
+            // Part 2
+            let group_priority = rucksack::badge_priorities(&rucksacks, layout)?;
 
-    // Part 2
-    let group_priority: i32 = include_str!("day3.txt")
-        .lines()
-        .map(|group| {
-            let group_vec: Vec<char> = group.chars().collect();
-            group_vec
-        })
-        .collect::<Vec<Vec<char>>>()
-        .chunks(3)
-        .into_iter()
-        .map(|triple| {
-            triple.into_iter().fold(
-                "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
-                    .chars()
-                    .collect(),
-                |acc, x| find_common_items(acc, x.to_owned()),
-            )
-        })
-        .into_iter()
-        .map(|ch| priority_lookup.get(&ch.first().unwrap()).unwrap())
-        .sum();
+            println!("Part 2: {:#?}", group_priority);
+        }
+        Mode::Validate => {
+            let violations = validate::validate(&rucksacks, layout)?;
+            for violation in &violations {
+                println!("{violation}");
+            }
+            if !violations.is_empty() {
+                return Err(format!("{} violations found", violations.len()));
+            }
+            println!("All rucksacks and groups share exactly one item type");
+        }
+        Mode::Plan => {
+            let plan = plan::plan(&rucksacks, layout, across_groups)?;
+            for item_move in &plan.moves {
+                println!("{item_move}");
+            }
+            for line in &plan.infeasible {
+                println!("line {line}: cannot be reorganised");
+            }
+            println!();
+            println!("Item moves: {}", plan.item_moves());
+            println!(
+                "Part 1 priority: {} -> {} (saves {})",
+                plan.priority_before,
+                plan.priority_after,
+                plan.priority_before - plan.priority_after
+            );
+        }
+        Mode::Analytics => {
+            let analytics = analytics::analyse(&rucksacks, layout)?;
+            match csv {
+                Some(Csv::Items) => print!("{}", analytics.items_csv()),
+                Some(Csv::Cooccurrence) => print!("{}", analytics.cooccurrence_csv()),
+                None => analytics.print_summary(10),
+            }
+        }
+    }
 
-    println!("Part 2: {:#?}", group_priority);
+    Ok(())
 }
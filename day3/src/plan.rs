@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::items::ItemSet;
+use crate::rucksack::{common_items, compartments, groups, Layout};
+
+/// Number of each item type held, indexed by priority (index 0 is unused)
+type Counts = [u32; 53];
+
+/// A compartment of a rucksack (both 1-based)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub compartment: usize,
+}
+
+/// Moving 'count' items of one type from one compartment to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub item: char,
+    pub count: u32,
+    pub from: Location,
+    pub to: Location,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} '{}' from line {} compartment {} to line {} compartment {}",
+            self.count,
+            self.item,
+            self.from.line,
+            self.from.compartment,
+            self.to.line,
+            self.to.compartment
+        )
+    }
+}
+
+/// The reorganisation of every rucksack in the list
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    /// Lines of rucksacks that could not be reorganised
+    pub infeasible: Vec<usize>,
+    /// Part 1 priority total before the moves
+    pub priority_before: u32,
+    /// Part 1 priority total after the moves
+    pub priority_after: u32,
+}
+
+impl Plan {
+    /// Total number of single item moves
+    pub fn item_moves(&self) -> u32 {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+}
+
+/// Per compartment item counts of a rucksack
+fn count_items(rucksack: &str, layout: Layout) -> Result<Vec<Counts>, String> {
+    compartments(rucksack, layout.compartments)?
+        .iter()
+        .map(|part| {
+            let mut counts = [0; 53];
+            for item in part.chars() {
+                let p = ItemSet::priority(item)
+                    .ok_or_else(|| format!("'{item}' is not an item type"))?;
+                counts[p as usize] += 1;
+            }
+            Ok(counts)
+        })
+        .collect()
+}
+
+fn item_set(counts: &Counts) -> ItemSet {
+    (1..53)
+        .filter(|&p| counts[p] > 0)
+        .fold(ItemSet::EMPTY, |mut set, p| {
+            set.insert(ItemSet::item(p as u32));
+            set
+        })
+}
+
+/// Every item type held anywhere in a rucksack
+fn rucksack_items(contents: &[Counts]) -> ItemSet {
+    contents
+        .iter()
+        .map(item_set)
+        .fold(ItemSet::EMPTY, ItemSet::union)
+}
+
+/// Part 1 priority of a rucksack: its lowest priority item type found in every compartment
+fn shared_priority(contents: &[Counts]) -> u32 {
+    let sets: Vec<ItemSet> = contents.iter().map(item_set).collect();
+    common_items(&sets).priorities().next().unwrap_or(0)
+}
+
+/// Choose one compartment for every item type so that each compartment keeps its size,
+/// keeping as many items in place as possible. Returns the chosen compartment per
+/// priority and the number of items that have to move, or None if no arrangement exists.
+///
+/// This is a dynamic program over item types, with the fill level of each compartment
+/// as the state.
+fn arrange(contents: &[Counts]) -> Option<(Vec<usize>, u32)> {
+    let k = contents.len();
+    let size: u32 = contents[0].iter().sum();
+    let total = |p: usize| contents.iter().map(|c| c[p]).sum::<u32>();
+
+    // fill levels -> (items kept in place, compartment chosen per priority)
+    let mut states: HashMap<Vec<u32>, (u32, Vec<usize>)> = HashMap::new();
+    states.insert(vec![0; k], (0, vec![0; 53]));
+
+    for p in (1..53).filter(|&p| total(p) > 0) {
+        let mut next: HashMap<Vec<u32>, (u32, Vec<usize>)> = HashMap::new();
+        for (fill, (kept, choice)) in &states {
+            for c in 0..k {
+                if fill[c] + total(p) > size {
+                    continue;
+                }
+                let mut fill = fill.clone();
+                fill[c] += total(p);
+                let kept = kept + contents[c][p];
+                if next.get(&fill).is_none_or(|(best, _)| kept > *best) {
+                    let mut choice = choice.clone();
+                    choice[p] = c;
+                    next.insert(fill, (kept, choice));
+                }
+            }
+        }
+        states = next;
+    }
+
+    states
+        .remove(&vec![size; k])
+        .map(|(kept, choice)| (choice, size * k as u32 - kept))
+}
+
+/// Moves that put every item of a rucksack into its chosen compartment
+fn moves_for(line: usize, contents: &[Counts], choice: &[usize]) -> Vec<Move> {
+    let mut moves = vec![];
+    for (c, counts) in contents.iter().enumerate() {
+        for p in 1..53 {
+            if counts[p] > 0 && choice[p] != c {
+                moves.push(Move {
+                    item: ItemSet::item(p as u32),
+                    count: counts[p],
+                    from: Location {
+                        line,
+                        compartment: c + 1,
+                    },
+                    to: Location {
+                        line,
+                        compartment: choice[p] + 1,
+                    },
+                });
+            }
+        }
+    }
+    moves
+}
+
+/// Per compartment item counts of a rucksack once every item is in its chosen compartment
+fn arranged(contents: &[Counts], choice: &[usize]) -> Vec<Counts> {
+    let mut after = vec![[0; 53]; contents.len()];
+    for counts in contents {
+        for p in 1..53 {
+            after[choice[p]][p] += counts[p];
+        }
+    }
+    after
+}
+
+/// Items that must move to reorganise a rucksack, or None if it cannot be reorganised
+fn cost(contents: &[Counts]) -> Option<u32> {
+    arrange(contents).map(|(_, moved)| moved)
+}
+
+/// An exchange of one item between two rucksacks of a group
+struct Exchange {
+    /// Index within the group of the other rucksack
+    other: usize,
+    give: (usize, usize),
+    take: (usize, usize),
+    /// Item moves this costs, including the exchange itself
+    moves: u32,
+}
+
+/// Find the cheapest single exchange of one item between rucksack 'r' and another
+/// member of its group that leaves both able to be reorganised. Only exchanges of one
+/// item are tried, so a rucksack that needs more than that stays infeasible. Badge item types are
+/// never exchanged, and the group must still share exactly its original badges.
+fn best_exchange(group: &[Vec<Counts>], r: usize) -> Option<Exchange> {
+    let sets: Vec<ItemSet> = group
+        .iter()
+        .map(|contents| rucksack_items(contents))
+        .collect();
+    let badges = common_items(&sets);
+
+    let mut best: Option<Exchange> = None;
+    for (s, other) in group.iter().enumerate().filter(|&(s, _)| s != r) {
+        let Some(other_cost) = cost(other) else {
+            continue;
+        };
+        for (cx, x) in positions(&group[r], badges) {
+            for (cy, y) in positions(other, badges) {
+                if x == y {
+                    continue;
+                }
+                let mut mine = group[r].clone();
+                let mut theirs = other.clone();
+                mine[cx][x] -= 1;
+                mine[cx][y] += 1;
+                theirs[cy][y] -= 1;
+                theirs[cy][x] += 1;
+
+                let mut after = sets.clone();
+                after[r] = rucksack_items(&mine);
+                after[s] = rucksack_items(&theirs);
+                if common_items(&after) != badges {
+                    continue;
+                }
+
+                if let (Some(a), Some(b)) = (cost(&mine), cost(&theirs)) {
+                    // The other rucksack's own reorganisation is paid for either way
+                    let moves = 2 + a + b - other_cost.min(b);
+                    if best.as_ref().is_none_or(|e| moves < e.moves) {
+                        best = Some(Exchange {
+                            other: s,
+                            give: (cx, x),
+                            take: (cy, y),
+                            moves,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// (compartment, priority) of every non badge item type held in a rucksack
+fn positions(contents: &[Counts], badges: ItemSet) -> Vec<(usize, usize)> {
+    contents
+        .iter()
+        .enumerate()
+        .flat_map(|(c, counts)| (1..53).filter(move |&p| counts[p] > 0).map(move |p| (c, p)))
+        .filter(|&(_, p)| !badges.contains(ItemSet::item(p as u32)))
+        .collect()
+}
+
+/// Plan the fewest item moves so that no item type is in more than one compartment of
+/// the same rucksack, keeping every compartment the same size. With 'across_groups',
+/// a rucksack that cannot be fixed on its own may exchange one item with another
+/// rucksack of its group (badges are left alone so part 2 is unaffected). This is a
+/// greedy heuristic: each such rucksack takes the cheapest single exchange in turn, so
+/// the plan is only the fewest moves for the rucksacks fixed within themselves.
+pub fn plan(rucksacks: &[&str], layout: Layout, across_groups: bool) -> Result<Plan, String> {
+    let mut contents = rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, rucksack)| {
+            count_items(rucksack, layout).map_err(|e| format!("line {}: {e}", i + 1))
+        })
+        .collect::<Result<Vec<Vec<Counts>>, String>>()?;
+
+    let mut plan = Plan {
+        priority_before: contents.iter().map(|c| shared_priority(c)).sum(),
+        ..Plan::default()
+    };
+
+    if across_groups {
+        let group_count = groups(rucksacks, layout.group_size)?.len();
+        for g in 0..group_count {
+            let start = g * layout.group_size;
+            for r in 0..layout.group_size {
+                let group = &contents[start..start + layout.group_size];
+                if cost(&group[r]).is_some() {
+                    continue;
+                }
+                if let Some(exchange) = best_exchange(group, r) {
+                    let (mine, theirs) = (start + r, start + exchange.other);
+                    let ((cx, x), (cy, y)) = (exchange.give, exchange.take);
+                    contents[mine][cx][x] -= 1;
+                    contents[mine][cx][y] += 1;
+                    contents[theirs][cy][y] -= 1;
+                    contents[theirs][cy][x] += 1;
+
+                    let at = |line: usize, compartment: usize| Location {
+                        line: line + 1,
+                        compartment: compartment + 1,
+                    };
+                    plan.moves.push(Move {
+                        item: ItemSet::item(x as u32),
+                        count: 1,
+                        from: at(mine, cx),
+                        to: at(theirs, cy),
+                    });
+                    plan.moves.push(Move {
+                        item: ItemSet::item(y as u32),
+                        count: 1,
+                        from: at(theirs, cy),
+                        to: at(mine, cx),
+                    });
+                }
+            }
+        }
+    }
+
+    for (i, rucksack) in contents.iter().enumerate() {
+        match arrange(rucksack) {
+            Some((choice, _)) => {
+                plan.moves.extend(moves_for(i + 1, rucksack, &choice));
+                plan.priority_after += shared_priority(&arranged(rucksack, &choice));
+            }
+            None => {
+                plan.infeasible.push(i + 1);
+                plan.priority_after += shared_priority(rucksack);
+            }
+        }
+    }
+
+    Ok(plan)
+}
@@ -0,0 +1,104 @@
+use crate::items::ItemSet;
+
+/// How rucksacks are split into compartments and grouped into elf groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// Number of equally sized compartments in each rucksack
+    pub compartments: usize,
+    /// Number of elves (consecutive lines) in each badge group
+    pub group_size: usize,
+}
+
+impl Default for Layout {
+    fn default() -> Layout {
+        Layout {
+            compartments: 2,
+            group_size: 3,
+        }
+    }
+}
+
+/// Split a rucksack into 'count' equally sized compartments
+pub fn compartments(rucksack: &str, count: usize) -> Result<Vec<&str>, String> {
+    if count == 0 {
+        return Err("compartment count must be at least 1".to_string());
+    }
+    if !rucksack.is_ascii() {
+        return Err(format!("'{rucksack}' contains non-ASCII characters"));
+    }
+    if !rucksack.len().is_multiple_of(count) {
+        return Err(format!(
+            "{} items cannot be split into {count} equal compartments",
+            rucksack.len()
+        ));
+    }
+
+    let size = rucksack.len() / count;
+    Ok((0..count)
+        .map(|i| &rucksack[i * size..(i + 1) * size])
+        .collect())
+}
+
+/// Split the rucksack list into consecutive groups of 'size' elves
+pub fn groups<'a>(rucksacks: &'a [&'a str], size: usize) -> Result<Vec<&'a [&'a str]>, String> {
+    if size == 0 {
+        return Err("group size must be at least 1".to_string());
+    }
+    if !rucksacks.len().is_multiple_of(size) {
+        return Err(format!(
+            "{} rucksacks cannot be split into groups of {size}",
+            rucksacks.len()
+        ));
+    }
+
+    Ok(rucksacks.chunks(size).collect())
+}
+
+/// Item types present in every one of 'sets'
+pub fn common_items(sets: &[ItemSet]) -> ItemSet {
+    sets.iter()
+        .fold(ItemSet::ALL, |acc, &set| acc.intersection(set))
+}
+
+/// Priority of the item type shared by every one of 'parts' (compartments or rucksacks)
+fn shared_priority(parts: &[&str]) -> Result<u32, String> {
+    let sets = parts
+        .iter()
+        .map(|part| ItemSet::parse(part))
+        .collect::<Result<Vec<ItemSet>, String>>()?;
+
+    common_items(&sets)
+        .priorities()
+        .next()
+        .ok_or_else(|| "no item type is shared".to_string())
+}
+
+/// Part 1: sum of the priorities of the item type found in every compartment of each rucksack
+pub fn compartment_priorities(rucksacks: &[&str], layout: Layout) -> Result<u32, String> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, rucksack)| {
+            compartments(rucksack, layout.compartments)
+                .and_then(|parts| shared_priority(&parts))
+                .map_err(|e| format!("line {}: {e}", i + 1))
+        })
+        .sum()
+}
+
+/// Part 2: sum of the priorities of the badge (the item type carried by every elf) of each group
+pub fn badge_priorities(rucksacks: &[&str], layout: Layout) -> Result<u32, String> {
+    groups(rucksacks, layout.group_size)?
+        .iter()
+        .enumerate()
+        .map(|(g, group)| {
+            let first_line = g * layout.group_size + 1;
+            shared_priority(group).map_err(|e| {
+                format!(
+                    "group at lines {first_line}-{}: {e}",
+                    first_line + layout.group_size - 1
+                )
+            })
+        })
+        .sum()
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+use crate::items::ItemSet;
+use crate::rucksack::{common_items, compartments, groups, Layout};
+
+/// Where a rule violation was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// The compartments of the rucksack on this (1-based) line
+    Rucksack { line: usize },
+    /// The badge group spanning these (1-based, inclusive) lines
+    Group { first_line: usize, last_line: usize },
+}
+
+/// A rucksack or group that does not share exactly one item type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub scope: Scope,
+    pub shared: ItemSet,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.scope {
+            Scope::Rucksack { line } => write!(f, "line {line}: compartments share ")?,
+            Scope::Group {
+                first_line,
+                last_line,
+            } => write!(
+                f,
+                "group at lines {first_line}-{last_line}: rucksacks share "
+            )?,
+        }
+        if self.shared.is_empty() {
+            write!(f, "no item types")
+        } else {
+            let items: String = self.shared.iter().collect();
+            write!(f, "{} item types ({items})", self.shared.len())
+        }
+    }
+}
+
+fn parse_all(parts: &[&str]) -> Result<Vec<ItemSet>, String> {
+    parts.iter().map(|part| ItemSet::parse(part)).collect()
+}
+
+/// Check that every rucksack's compartments, and every group's rucksacks, share exactly
+/// one item type. Malformed input (bad items, uneven compartments or groups) is an error,
+/// while rule violations are collected and returned in line order.
+pub fn validate(rucksacks: &[&str], layout: Layout) -> Result<Vec<Violation>, String> {
+    let mut violations = vec![];
+
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let sets = compartments(rucksack, layout.compartments)
+            .and_then(|parts| parse_all(&parts))
+            .map_err(|e| format!("line {}: {e}", i + 1))?;
+        let shared = common_items(&sets);
+        if shared.len() != 1 {
+            violations.push(Violation {
+                scope: Scope::Rucksack { line: i + 1 },
+                shared,
+            });
+        }
+    }
+
+    for (g, group) in groups(rucksacks, layout.group_size)?.iter().enumerate() {
+        let shared = common_items(&parse_all(group)?);
+        if shared.len() != 1 {
+            let first_line = g * layout.group_size + 1;
+            violations.push(Violation {
+                scope: Scope::Group {
+                    first_line,
+                    last_line: first_line + layout.group_size - 1,
+                },
+                shared,
+            });
+        }
+    }
+
+    Ok(violations)
+}
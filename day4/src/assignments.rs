@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::interval::Interval;
+
+/// The two section assignments on one line of the list
+pub type Pair = (Interval<u32>, Interval<u32>);
+
+/// An elf, identified by its (1-based) line and its position (1 or 2) within the pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Elf {
+    pub line: usize,
+    pub position: usize,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {} elf {}", self.line, self.position)
+    }
+}
+
+/// The sections one elf has been assigned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    pub elf: Elf,
+    pub sections: Interval<u32>,
+}
+
+/// Parse a line of the form "13-53,17-82" into the pair of section assignments
+pub fn parse_line(line: &str) -> Result<Pair, String> {
+    let (left, right) = line
+        .split_once(',')
+        .ok_or_else(|| format!("'{line}' is not a pair of assignments"))?;
+
+    Ok((Interval::parse(left)?, Interval::parse(right)?))
+}
+
+/// Parse every line of the list, reporting the first bad line by number
+pub fn parse_pairs(input: &str) -> Result<Vec<Pair>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+/// Every elf's assignment, in list order
+pub fn assignments(pairs: &[Pair]) -> Vec<Assignment> {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &(left, right))| {
+            [(1, left), (2, right)].map(|(position, sections)| Assignment {
+                elf: Elf {
+                    line: i + 1,
+                    position,
+                },
+                sections,
+            })
+        })
+        .collect()
+}
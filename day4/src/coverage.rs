@@ -0,0 +1,81 @@
+use crate::interval::Interval;
+
+/// A run of consecutive sections that are all covered by the same number of elves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub sections: Interval<u32>,
+    pub depth: usize,
+}
+
+/// Merge every assignment into runs of constant coverage depth with a sweep line, from the
+/// first covered section to the last. Runs with depth 0 are the gaps. This sorts 2n events
+/// and makes one pass, so it is O(n log n) however many assignments overlap.
+pub fn sweep(assignments: &[Interval<u32>]) -> Vec<Segment> {
+    let mut events: Vec<(u64, i64)> = assignments
+        .iter()
+        .filter_map(Interval::values)
+        .flat_map(|sections| [(*sections.start(), 1), (sections.end() + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let mut segments = vec![];
+    let mut depth: i64 = 0;
+    let mut i = 0;
+    while i < events.len() {
+        let position = events[i].0;
+        while i < events.len() && events[i].0 == position {
+            depth += events[i].1;
+            i += 1;
+        }
+        if let Some(&(next, _)) = events.get(i) {
+            segments.push(Segment {
+                // Every event is at most u32::MAX + 1, so the last section fits in a u32
+                sections: Interval::closed(position as u32, (next - 1) as u32).unwrap(),
+                depth: depth as usize,
+            });
+        }
+    }
+
+    segments
+}
+
+/// Summary of how the sections are covered by all assignments together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Sections covered by at least one elf
+    pub covered: u128,
+    /// Uncovered runs between the first and last covered sections
+    pub gaps: Vec<Interval<u32>>,
+    /// Sections covered by more than 'threshold' elves
+    pub above_threshold: u128,
+    pub threshold: usize,
+    /// Largest number of elves covering one section, and the first run at that depth
+    pub max_depth: usize,
+    pub deepest: Option<Interval<u32>>,
+}
+
+pub fn report(assignments: &[Interval<u32>], threshold: usize) -> Report {
+    let segments = sweep(assignments);
+    let length = |segment: &Segment| segment.sections.len();
+
+    let max_depth = segments.iter().map(|s| s.depth).max().unwrap_or(0);
+    Report {
+        covered: segments.iter().filter(|s| s.depth > 0).map(length).sum(),
+        gaps: segments
+            .iter()
+            .filter(|s| s.depth == 0)
+            .map(|s| s.sections)
+            .collect(),
+        above_threshold: segments
+            .iter()
+            .filter(|s| s.depth > threshold)
+            .map(length)
+            .sum(),
+        threshold,
+        max_depth,
+        deepest: segments
+            .iter()
+            .find(|s| max_depth > 0 && s.depth == max_depth)
+            .map(|s| s.sections),
+    }
+}
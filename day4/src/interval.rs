@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A range of values between two bounds, each of which may be inclusive or exclusive.
+/// The start can never be after the end, so reversed ranges like "9-3" are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+    start_inclusive: bool,
+    end_inclusive: bool,
+}
+
+impl<T: Ord> Interval<T> {
+    /// An interval with the given bounds, or an error if 'start' is after 'end'
+    pub fn with_bounds(
+        start: T,
+        start_inclusive: bool,
+        end: T,
+        end_inclusive: bool,
+    ) -> Result<Interval<T>, String> {
+        if start > end {
+            return Err("interval start is after its end".to_string());
+        }
+        Ok(Interval {
+            start,
+            end,
+            start_inclusive,
+            end_inclusive,
+        })
+    }
+
+    /// [start, end], as used by the section assignments
+    pub fn closed(start: T, end: T) -> Result<Interval<T>, String> {
+        Interval::with_bounds(start, true, end, true)
+    }
+
+    /// [start, end)
+    pub fn half_open(start: T, end: T) -> Result<Interval<T>, String> {
+        Interval::with_bounds(start, true, end, false)
+    }
+
+    /// (start, end)
+    pub fn open(start: T, end: T) -> Result<Interval<T>, String> {
+        Interval::with_bounds(start, false, end, false)
+    }
+
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    pub fn start_inclusive(&self) -> bool {
+        self.start_inclusive
+    }
+
+    pub fn end_inclusive(&self) -> bool {
+        self.end_inclusive
+    }
+
+    /// True if no value lies in the interval, e.g. [3, 3)
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end && !(self.start_inclusive && self.end_inclusive)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let after_start = match self.start.cmp(value) {
+            Ordering::Less => true,
+            Ordering::Equal => self.start_inclusive,
+            Ordering::Greater => false,
+        };
+        let before_end = match value.cmp(&self.end) {
+            Ordering::Less => true,
+            Ordering::Equal => self.end_inclusive,
+            Ordering::Greater => false,
+        };
+        after_start && before_end
+    }
+
+    /// True if every value in 'other' is also in this interval
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        other.is_empty()
+            || (cmp_starts(self, other) != Ordering::Greater
+                && cmp_ends(self, other) != Ordering::Less)
+    }
+
+    /// True if at least one value is in both intervals
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        let (later_start, earlier_end) = (
+            max_by(self, other, cmp_starts),
+            min_by(self, other, cmp_ends),
+        );
+        match later_start.start.cmp(&earlier_end.end) {
+            Ordering::Less => true,
+            Ordering::Equal => later_start.start_inclusive && earlier_end.end_inclusive,
+            Ordering::Greater => false,
+        }
+    }
+}
+
+impl<T: Ord + Clone> Interval<T> {
+    /// Values in both intervals, or None if they do not overlap
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let later_start = max_by(self, other, cmp_starts);
+        let earlier_end = min_by(self, other, cmp_ends);
+        Some(Interval {
+            start: later_start.start.clone(),
+            start_inclusive: later_start.start_inclusive,
+            end: earlier_end.end.clone(),
+            end_inclusive: earlier_end.end_inclusive,
+        })
+    }
+
+    /// Values in either interval, or None if that is not a single interval (there is a
+    /// gap between them). Intervals that only touch, like [1, 3) and [3, 5], are joined.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() {
+            return Some(other.clone());
+        }
+        if other.is_empty() {
+            return Some(self.clone());
+        }
+        let earlier_end = min_by(self, other, cmp_ends);
+        let later_start = max_by(self, other, cmp_starts);
+        let joined = match earlier_end.end.cmp(&later_start.start) {
+            Ordering::Greater => true,
+            Ordering::Equal => earlier_end.end_inclusive || later_start.start_inclusive,
+            Ordering::Less => false,
+        };
+        if !joined {
+            return None;
+        }
+
+        let earlier_start = min_by(self, other, cmp_starts);
+        let later_end = max_by(self, other, cmp_ends);
+        Some(Interval {
+            start: earlier_start.start.clone(),
+            start_inclusive: earlier_start.start_inclusive,
+            end: later_end.end.clone(),
+            end_inclusive: later_end.end_inclusive,
+        })
+    }
+}
+
+impl<T: Copy + Into<u64>> Interval<T> {
+    /// The first and last whole values in the interval, or None if it holds none.
+    /// Widened to u64, so stepping past an exclusive bound at T::MAX cannot overflow.
+    pub fn values(&self) -> Option<RangeInclusive<u64>> {
+        let (start, end): (u64, u64) = (self.start.into(), self.end.into());
+        let first = if self.start_inclusive {
+            start
+        } else {
+            start.checked_add(1)?
+        };
+        let last = if self.end_inclusive {
+            end
+        } else {
+            end.checked_sub(1)?
+        };
+        (first <= last).then_some(first..=last)
+    }
+
+    /// Number of whole values in the interval, e.g. 3 for [2, 4] or 2 for [2, 4).
+    /// Counted in u128, as even [0, u64::MAX] holds more values than a u64 can count.
+    pub fn len(&self) -> u128 {
+        self.values()
+            .map_or(0, |values| u128::from(values.end() - values.start()) + 1)
+    }
+}
+
+/// Order two intervals by where they start, an inclusive start being earlier than an
+/// exclusive one at the same value
+fn cmp_starts<T: Ord>(a: &Interval<T>, b: &Interval<T>) -> Ordering {
+    a.start
+        .cmp(&b.start)
+        .then(b.start_inclusive.cmp(&a.start_inclusive))
+}
+
+/// Order two intervals by where they end, an exclusive end being earlier than an
+/// inclusive one at the same value
+fn cmp_ends<T: Ord>(a: &Interval<T>, b: &Interval<T>) -> Ordering {
+    a.end
+        .cmp(&b.end)
+        .then(a.end_inclusive.cmp(&b.end_inclusive))
+}
+
+fn max_by<'a, T>(
+    a: &'a Interval<T>,
+    b: &'a Interval<T>,
+    cmp: fn(&Interval<T>, &Interval<T>) -> Ordering,
+) -> &'a Interval<T> {
+    if cmp(a, b) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+}
+
+fn min_by<'a, T>(
+    a: &'a Interval<T>,
+    b: &'a Interval<T>,
+    cmp: fn(&Interval<T>, &Interval<T>) -> Ordering,
+) -> &'a Interval<T> {
+    if cmp(a, b) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T: Ord + FromStr> Interval<T> {
+    /// Parse a closed interval of the form "13-53"
+    pub fn parse(s: &str) -> Result<Interval<T>, String> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("'{s}' is not of the form start-end"))?;
+        let number = |v: &str| {
+            v.parse::<T>()
+                .map_err(|_| format!("'{v}' is not a valid bound"))
+        };
+        Interval::closed(number(start)?, number(end)?).map_err(|e| format!("'{s}': {e}"))
+    }
+}
+
+impl<T: Ord + FromStr> FromStr for Interval<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Interval<T>, String> {
+        Interval::parse(s)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.start_inclusive { '[' } else { '(' },
+            self.start,
+            self.end,
+            if self.end_inclusive { ']' } else { ')' }
+        )
+    }
+}
@@ -1,63 +1,173 @@
+use day4::assignments::{self, Pair};
+use day4::interval::Interval;
+use day4::{coverage, overlaps, reassign};
 
-/// Takes in a &str of the form "13-53" and returns a tuple of the integers e.g. (13, 53)
-fn parse_pair_to_numeric(pair: &str) -> (u32, u32) {
-    let parsed: Vec<u32> = pair
-        .split("-")
-        .into_iter()
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect();
-    
-    (*parsed.first().unwrap(), *parsed.last().unwrap())
+/// What the binary should do with the assignment list
+enum Mode {
+    /// Count fully and partially overlapping pairs
+    Parts,
+    /// Merge every assignment and report how the sections are covered
+    Coverage,
+    /// List every pair of elves, anywhere in the list, whose assignments overlap
+    Overlaps,
+    /// List the elves covering each requested section
+    Covering,
+    /// Shrink overlapping assignments so no pair overlaps, reporting the work saved
+    Reassign,
 }
 
-/// Returns true if the range ('l' or 'r') completely contains the other range
-fn complete_overlap(l1: u32, l2: u32, r1: u32, r2: u32) -> bool {
-    if (l1 <= r1 && r2 <= l2) || (r1 <= l1 && l2 <= r2) {
-        return true;
-    }
-    false
+/// Parse a numeric option value
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for {arg}"))
+}
+
+/// Command line options:
+/// `day4 [coverage|overlaps|covering|reassign] [--threshold N] [--section S]... [--input PATH]`
+struct Options {
+    mode: Mode,
+    threshold: usize,
+    sections: Vec<u32>,
+    input: String,
 }
 
-/// Returns true if there is a partial overlap of the 'l' and 'r' ranges
-fn partial_overlap(l1: u32, l2: u32, r1: u32, r2: u32) -> bool {
-    if (l1 <= r1 && r1 <= l2) || (l1 <= r2 && r2 <= l2) {
-        return true;
-    } else if (r1 <= l1 && l1 <= r2) || (r1 <= l2 && l2 <= r2) {
-        return true;
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            mode: Mode::Parts,
+            threshold: 1,
+            sections: vec![],
+            input: include_str!("day4.txt").to_string(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "coverage" => options.mode = Mode::Coverage,
+                "overlaps" => options.mode = Mode::Overlaps,
+                "covering" => options.mode = Mode::Covering,
+                "reassign" => options.mode = Mode::Reassign,
+                "--section" => options.sections.push(parse_number(&arg, &value()?)?),
+                "--threshold" => options.threshold = parse_number(&arg, &value()?)?,
+                "--input" => {
+                    let path = value()?;
+                    options.input = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("could not read {path}: {e}"))?;
+                }
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
     }
-    false
 }
 
+fn main() -> Result<(), String> {
+    let Options {
+        mode,
+        threshold,
+        sections,
+        input,
+    } = Options::from_args(std::env::args().skip(1))?;
+
+    let pairs: Vec<Pair> = assignments::parse_pairs(&input)?;
+
+    match mode {
+        Mode::Parts => {
+            // Part 1: Find fully overlapping pairs
+            let fully_overlapping_pairs = pairs
+                .iter()
+                .filter(|(left, right)| {
+                    left.contains_interval(right) || right.contains_interval(left)
+                })
+                .count();
+
+            println!("Part 1: {}", fully_overlapping_pairs);
+
+            // Part 2: Find partially overlapping pairs
+            let partially_overlapping_pairs = pairs
+                .iter()
+                .filter(|(left, right)| left.overlaps(right))
+                .count();
+
+            println!("Part 2: {}", partially_overlapping_pairs);
+        }
+        Mode::Coverage => {
+            let assignments: Vec<Interval<u32>> = assignments::assignments(&pairs)
+                .iter()
+                .map(|a| a.sections)
+                .collect();
+            let report = coverage::report(&assignments, threshold);
+
+            println!("Sections covered: {}", report.covered);
+            println!(
+                "Sections covered by more than {} elves: {}",
+                report.threshold, report.above_threshold
+            );
+            match report.deepest {
+                Some(sections) => {
+                    println!("Maximum overlap: {} elves at {sections}", report.max_depth)
+                }
+                None => println!("Maximum overlap: 0 elves"),
+            }
+            println!("Uncovered gaps: {}", report.gaps.len());
+            for gap in &report.gaps {
+                println!("  {gap}");
+            }
+        }
+        Mode::Overlaps => {
+            let assignments = assignments::assignments(&pairs);
+            let found = overlaps::find_overlaps(&assignments);
+            for overlap in &found {
+                let kind = match overlap.kind {
+                    overlaps::Kind::Full => "full",
+                    overlaps::Kind::Partial => "partial",
+                };
+                println!("{} and {}: {kind}", overlap.first, overlap.second);
+            }
+            let full = found
+                .iter()
+                .filter(|o| o.kind == overlaps::Kind::Full)
+                .count();
+            println!();
+            println!("Overlapping assignment pairs: {}", found.len());
+            println!("  full: {full}");
+            println!("  partial: {}", found.len() - full);
+        }
+        Mode::Covering => {
+            if sections.is_empty() {
+                return Err("covering needs at least one --section".to_string());
+            }
+            let index = overlaps::IntervalIndex::new(&assignments::assignments(&pairs));
+            for section in sections {
+                let elves = index.covering(section);
+                println!("Section {section}: {} elves", elves.len());
+                for elf in elves {
+                    println!("  {elf}");
+                }
+            }
+        }
+        Mode::Reassign => {
+            let reassignments = reassign::plan(&pairs);
+            for reassignment in &reassignments {
+                println!("{reassignment}");
+            }
+            let savings = reassign::savings(&pairs, &reassignments);
+            println!();
+            println!("Pairs reassigned: {}", reassignments.len());
+            println!(
+                "Sections worked: {} -> {} (saves {})",
+                savings.work_before,
+                savings.work_after,
+                savings.work_before - savings.work_after
+            );
+            println!("Elves left idle: {}", savings.idle_elves);
+        }
+    }
 
-fn main() {
-    
-    // Part 1: Find fully overlapping pairs
-    let fully_overlapping_pairs: u32 = include_str!("day4.txt")
-        .lines()
-        .map(|line| {
-            let (left, right) = line.split_once(",").unwrap();
-            let (l1, l2) = parse_pair_to_numeric(left);
-            let (r1, r2) = parse_pair_to_numeric(right);
-
-            complete_overlap(l1, l2, r1, r2)  // returns bool, true if complete overlap
-        })
-        .map(|overlap| if overlap {1} else {0})
-        .sum();
-
-    println!("Part 1: {}", fully_overlapping_pairs);
-
-    // Part 2: Find partially overlapping pairs
-    let partially_overlapping_pairs: u32 = include_str!("day4.txt")
-        .lines()
-        .map(|line| {
-            let (left, right) = line.split_once(",").unwrap();
-            let (l1, l2) = parse_pair_to_numeric(left);
-            let (r1, r2) = parse_pair_to_numeric(right);
-
-            partial_overlap(l1, l2, r1, r2)  // returns bool, true if complete overlap
-        })
-        .map(|overlap| if overlap {1} else {0})
-        .sum();
-
-    println!("Part 2: {}", partially_overlapping_pairs);
+    Ok(())
 }
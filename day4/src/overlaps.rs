@@ -0,0 +1,125 @@
+use crate::assignments::{Assignment, Elf};
+
+/// How two assignments overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// One assignment contains the other
+    Full,
+    /// They share some, but not all, sections
+    Partial,
+}
+
+/// Two elves, anywhere in the list, whose assignments overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    pub first: Elf,
+    pub second: Elf,
+    pub kind: Kind,
+}
+
+/// Find every pair of overlapping assignments with a sweep over the assignments sorted by
+/// start, keeping the ones that have not ended yet. Each kept assignment is (bar boundary
+/// cases) reported as an overlap and every other is dropped for good, so this is
+/// O(n log n + k) for k overlaps rather than O(n²).
+pub fn find_overlaps(assignments: &[Assignment]) -> Vec<Overlap> {
+    let mut sorted: Vec<&Assignment> = assignments
+        .iter()
+        .filter(|a| !a.sections.is_empty())
+        .collect();
+    sorted.sort_by_key(|a| (*a.sections.start(), a.elf));
+
+    let mut open: Vec<&Assignment> = vec![];
+    let mut overlaps = vec![];
+    for current in sorted {
+        open.retain(|a| a.sections.end() >= current.sections.start());
+        for a in &open {
+            let (x, y) = (&a.sections, &current.sections);
+            if !x.overlaps(y) {
+                continue;
+            }
+            overlaps.push(Overlap {
+                first: a.elf.min(current.elf),
+                second: a.elf.max(current.elf),
+                kind: if x.contains_interval(y) || y.contains_interval(x) {
+                    Kind::Full
+                } else {
+                    Kind::Partial
+                },
+            });
+        }
+        open.push(current);
+    }
+
+    overlaps.sort_by_key(|o| (o.first, o.second));
+    overlaps
+}
+
+/// A static interval tree for "which elves cover section S" queries. Assignments are
+/// sorted by start and the tree is implicit in that order: the node for a range is its
+/// middle element, and each node records the latest end in its subtree, so a query
+/// skips any subtree that ends before the section.
+pub struct IntervalIndex {
+    sorted: Vec<Assignment>,
+    max_end: Vec<u32>,
+}
+
+impl IntervalIndex {
+    pub fn new(assignments: &[Assignment]) -> IntervalIndex {
+        let mut sorted: Vec<Assignment> = assignments
+            .iter()
+            .filter(|a| !a.sections.is_empty())
+            .copied()
+            .collect();
+        sorted.sort_by_key(|a| *a.sections.start());
+
+        let mut index = IntervalIndex {
+            max_end: vec![0; sorted.len()],
+            sorted,
+        };
+        index.build(0, index.sorted.len());
+        index
+    }
+
+    /// Fill in max_end for the subtree over sorted[lo..hi], returning it
+    fn build(&mut self, lo: usize, hi: usize) -> Option<u32> {
+        if lo >= hi {
+            return None;
+        }
+        let mid = (lo + hi) / 2;
+        let own = *self.sorted[mid].sections.end();
+        let max = [self.build(lo, mid), self.build(mid + 1, hi)]
+            .into_iter()
+            .flatten()
+            .fold(own, u32::max);
+        self.max_end[mid] = max;
+        Some(max)
+    }
+
+    /// Elves whose assignment includes 'section', in list order
+    pub fn covering(&self, section: u32) -> Vec<Elf> {
+        let mut elves = vec![];
+        self.search(0, self.sorted.len(), section, &mut elves);
+        elves.sort();
+        elves
+    }
+
+    fn search(&self, lo: usize, hi: usize, section: u32, elves: &mut Vec<Elf>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if self.max_end[mid] < section {
+            return;
+        }
+        self.search(lo, mid, section, elves);
+        let assignment = &self.sorted[mid];
+        if *assignment.sections.start() <= section {
+            if assignment.sections.contains(&section) {
+                elves.push(assignment.elf);
+            }
+            // Everything to the right starts at or after this one, so only look there if
+            // this one did not already start after the section
+            self.search(mid + 1, hi, section, elves);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::assignments::Pair;
+use crate::interval::Interval;
+
+/// Sections assigned to one elf after reassignment, or None if it has nothing left to do
+pub type Sections = Option<Interval<u32>>;
+
+/// A pair whose assignments were shrunk so the elves no longer overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reassignment {
+    pub line: usize,
+    pub before: Pair,
+    pub after: (Sections, Sections),
+}
+
+/// Number of sections assigned
+fn count(sections: &Sections) -> u128 {
+    sections.map_or(0, |s| s.len())
+}
+
+fn describe(sections: &Sections) -> String {
+    match sections {
+        Some(s) => format!("{}-{}", s.start(), s.end()),
+        None => "idle".to_string(),
+    }
+}
+
+impl fmt::Display for Reassignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {},{} -> {},{}",
+            self.line,
+            describe(&Some(self.before.0)),
+            describe(&Some(self.before.1)),
+            describe(&self.after.0),
+            describe(&self.after.1)
+        )
+    }
+}
+
+/// Sections of closed interval 'x' that are not in closed interval 'y', or None if they
+/// would be split into two separate ranges
+fn remainder(x: &Interval<u32>, y: &Interval<u32>) -> Option<Sections> {
+    let Some(shared) = x.intersection(y) else {
+        return Some(Some(*x));
+    };
+    let before = shared.start() > x.start();
+    let after = shared.end() < x.end();
+    match (before, after) {
+        (true, true) => None,
+        (true, false) => Some(Some(
+            Interval::closed(*x.start(), shared.start() - 1).unwrap(),
+        )),
+        (false, true) => Some(Some(Interval::closed(shared.end() + 1, *x.end()).unwrap())),
+        (false, false) => Some(None),
+    }
+}
+
+/// Shrink one of the pair so the two no longer overlap, keeping the sections they cover
+/// between them the same. Every such reassignment removes exactly the overlapping
+/// sections, so this picks the least disruptive: only one elf changes, nobody is left
+/// idle if that can be avoided, and otherwise the elf with the longer range gives way.
+pub fn reassign(pair: &Pair) -> (Sections, Sections) {
+    let (left, right) = pair;
+    if !left.overlaps(right) {
+        return (Some(*left), Some(*right));
+    }
+
+    let shrink_left = remainder(left, right).map(|l| (l, Some(*right)));
+    let shrink_right = remainder(right, left).map(|r| (Some(*left), r));
+    let left_gives_way = count(&Some(*left)) >= count(&Some(*right));
+
+    [shrink_left, shrink_right]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(l, r)| {
+            let leaves_idle = l.is_none() || r.is_none();
+            let shrinks_shorter =
+                (l != &Some(*left) && !left_gives_way) || (r != &Some(*right) && left_gives_way);
+            (leaves_idle, shrinks_shorter)
+        })
+        .unwrap()
+}
+
+/// Reassignments for every overlapping pair in the list
+pub fn plan(pairs: &[Pair]) -> Vec<Reassignment> {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left.overlaps(right))
+        .map(|(i, pair)| Reassignment {
+            line: i + 1,
+            before: *pair,
+            after: reassign(pair),
+        })
+        .collect()
+}
+
+/// Section-visits before and after reassignment, and how many elves end up idle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Savings {
+    pub work_before: u128,
+    pub work_after: u128,
+    pub idle_elves: usize,
+}
+
+pub fn savings(pairs: &[Pair], reassignments: &[Reassignment]) -> Savings {
+    let work_before = pairs
+        .iter()
+        .map(|(l, r)| count(&Some(*l)) + count(&Some(*r)))
+        .sum();
+    let removed: u128 = reassignments
+        .iter()
+        .map(|r| {
+            count(&Some(r.before.0)) + count(&Some(r.before.1))
+                - count(&r.after.0)
+                - count(&r.after.1)
+        })
+        .sum();
+
+    Savings {
+        work_before,
+        work_after: work_before - removed,
+        idle_elves: reassignments
+            .iter()
+            .map(|r| usize::from(r.after.0.is_none()) + usize::from(r.after.1.is_none()))
+            .sum(),
+    }
+}
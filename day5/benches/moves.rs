@@ -0,0 +1,146 @@
+//! Compares applying a long procedure with the in-place cranes against the previous
+//! approach, which cloned the source and destination stacks for every instruction.
+//!
+//! Run with `cargo bench`.
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use day5::crane::{self, Crane, CrateMover9000, CrateMover9001};
+use day5::diagram::Stack;
+use day5::instruction::Instruction;
+
+const STACKS: usize = 9;
+const CRATES_PER_STACK: usize = 2_000;
+const INSTRUCTIONS: usize = 50_000;
+
+/// Small deterministic generator, so every run benchmarks the same procedure
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((self.0 >> 33) % n as u64) as usize
+    }
+}
+
+fn initial_stacks() -> Vec<Stack> {
+    (0..STACKS)
+        .map(|s| {
+            (0..CRATES_PER_STACK)
+                .map(|i| ((b'A' + ((s + i) % 26) as u8) as char).to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// Tall stacks and a procedure of valid moves between them
+fn generate() -> (Vec<Stack>, Vec<Instruction>) {
+    let mut rng = Lcg(42);
+    // Track the stacks while generating, so every move has enough crates to lift
+    let mut stacks = initial_stacks();
+
+    let mut instructions = vec![];
+    while instructions.len() < INSTRUCTIONS {
+        let source = rng.below(STACKS);
+        let destination = rng.below(STACKS);
+        if source == destination || stacks[source].is_empty() {
+            continue;
+        }
+        let volume = 1 + rng.below(stacks[source].len().min(30));
+        let instruction = Instruction {
+            volume,
+            source: source + 1,
+            destination: destination + 1,
+        };
+        crane::apply(&CrateMover9001, &mut stacks, &[instruction]).unwrap();
+        instructions.push(instruction);
+    }
+
+    (initial_stacks(), instructions)
+}
+
+/// The previous implementation: stacks with the top crate at the front, cloned out and
+/// written back for every instruction, with part 2 going through a staging deque
+fn apply_cloning(stacks: &[Stack], instructions: &[Instruction], keep_order: bool) -> String {
+    let mut stacks: Vec<VecDeque<String>> = stacks
+        .iter()
+        .map(|stack| stack.iter().rev().cloned().collect())
+        .collect();
+
+    for instruction in instructions {
+        let (volume, source, destination) = (
+            instruction.volume,
+            instruction.source,
+            instruction.destination,
+        );
+        let mut source_stack = stacks.get_mut(source - 1).unwrap().clone();
+        let mut destination_stack = stacks.get_mut(destination - 1).unwrap().clone();
+        if keep_order {
+            let mut staging_deque: VecDeque<String> = VecDeque::new();
+            for _ in 0..volume {
+                let c = source_stack.pop_front().unwrap();
+                staging_deque.push_front(c);
+            }
+            for _ in 0..volume {
+                let c = staging_deque.pop_front().unwrap();
+                destination_stack.push_front(c);
+            }
+        } else {
+            for _ in 0..volume {
+                let c = source_stack.pop_front().unwrap();
+                destination_stack.push_front(c);
+            }
+        }
+        stacks[source - 1] = source_stack.to_owned();
+        stacks[destination - 1] = destination_stack.to_owned();
+    }
+
+    stacks
+        .iter()
+        .filter_map(|s| s.front())
+        .map(String::as_str)
+        .collect()
+}
+
+fn apply_in_place(stacks: &[Stack], instructions: &[Instruction], crane: &dyn Crane) -> String {
+    let mut stacks = stacks.to_vec();
+    crane::apply(crane, &mut stacks, instructions).unwrap();
+    stacks
+        .iter()
+        .filter_map(|s| s.last())
+        .map(String::as_str)
+        .collect()
+}
+
+fn time<F: FnMut() -> String>(mut f: F) -> (Duration, String) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (start.elapsed(), result)
+}
+
+fn main() {
+    let (stacks, instructions) = generate();
+    println!("{STACKS} stacks of {CRATES_PER_STACK} crates, {INSTRUCTIONS} instructions");
+
+    for (crane, keep_order) in [
+        (&CrateMover9000 as &dyn Crane, false),
+        (&CrateMover9001 as &dyn Crane, true),
+    ] {
+        let (cloning, expected) = time(|| apply_cloning(&stacks, &instructions, keep_order));
+        let (in_place, actual) = time(|| apply_in_place(&stacks, &instructions, crane));
+        assert_eq!(expected, actual, "{} gave a different result", crane.name());
+
+        println!(
+            "{:<16} cloning: {:>10.2?}  in place: {:>10.2?}  ({:.0}x faster)",
+            crane.name(),
+            cloning,
+            in_place,
+            cloning.as_secs_f64() / in_place.as_secs_f64()
+        );
+    }
+}
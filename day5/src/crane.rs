@@ -0,0 +1,118 @@
+use crate::diagram::Stack;
+use crate::instruction::Instruction;
+
+/// A crane model, which decides how a number of crates get from one stack to another
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Move the top 'volume' crates of 'source' onto 'destination'
+    fn lift(&self, source: &mut Stack, destination: &mut Stack, volume: usize);
+}
+
+/// Moves crates one at a time, so a multi-crate move reverses their order
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn lift(&self, source: &mut Stack, destination: &mut Stack, volume: usize) {
+        // Taking crates off one by one puts them down in reverse order
+        let from = source.len() - volume;
+        destination.extend(source.drain(from..).rev());
+    }
+}
+
+/// Moves all the crates of an instruction at once, so they keep their order
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn lift(&self, source: &mut Stack, destination: &mut Stack, volume: usize) {
+        let from = source.len() - volume;
+        destination.extend(source.drain(from..));
+    }
+}
+
+/// Wraps another crane that can lift at most 'capacity' crates at once, so a bigger
+/// move is done as several smaller lifts from the top down
+pub struct CapacityLimited<C: Crane> {
+    pub crane: C,
+    pub capacity: usize,
+}
+
+impl<C: Crane> Crane for CapacityLimited<C> {
+    fn name(&self) -> String {
+        format!("{} (max {} per lift)", self.crane.name(), self.capacity)
+    }
+
+    fn lift(&self, source: &mut Stack, destination: &mut Stack, volume: usize) {
+        let mut remaining = volume;
+        while remaining > 0 {
+            let chunk = remaining.min(self.capacity);
+            self.crane.lift(source, destination, chunk);
+            remaining -= chunk;
+        }
+    }
+}
+
+/// Look up a crane model by name: "9000", "9001", or "9001:N" for a CrateMover 9001
+/// that can lift at most N crates at a time
+pub fn by_name(name: &str) -> Result<Box<dyn Crane>, String> {
+    match name.split_once(':') {
+        None => match name {
+            "9000" => Ok(Box::new(CrateMover9000)),
+            "9001" => Ok(Box::new(CrateMover9001)),
+            _ => Err(format!(
+                "unknown crane '{name}' (expected 9000, 9001 or 9001:N)"
+            )),
+        },
+        Some(("9001", capacity)) => match capacity.parse::<usize>() {
+            Ok(capacity) if capacity > 0 => Ok(Box::new(CapacityLimited {
+                crane: CrateMover9001,
+                capacity,
+            })),
+            _ => Err(format!("invalid crane capacity '{capacity}'")),
+        },
+        Some(_) => Err(format!(
+            "unknown crane '{name}' (expected 9000, 9001 or 9001:N)"
+        )),
+    }
+}
+
+/// Mutable references to two different stacks
+pub(crate) fn two_stacks(stacks: &mut [Stack], a: usize, b: usize) -> (&mut Stack, &mut Stack) {
+    if a < b {
+        let (low, high) = stacks.split_at_mut(b);
+        (&mut low[a], &mut high[0])
+    } else {
+        let (low, high) = stacks.split_at_mut(a);
+        (&mut high[0], &mut low[b])
+    }
+}
+
+/// Apply every instruction to the stacks using 'crane', moving crates in place.
+/// Moving crates from a stack onto itself leaves it as it was. Stops at the first
+/// instruction that names a missing stack or lifts more crates than the source holds.
+pub fn apply(
+    crane: &dyn Crane,
+    stacks: &mut [Stack],
+    instructions: &[Instruction],
+) -> Result<(), String> {
+    for (i, instruction) in instructions.iter().enumerate() {
+        instruction
+            .validate(stacks)
+            .map_err(|e| format!("instruction {} ({instruction}): {e}", i + 1))?;
+        if instruction.source == instruction.destination {
+            continue;
+        }
+        let (source, destination) =
+            two_stacks(stacks, instruction.source - 1, instruction.destination - 1);
+        crane.lift(source, destination, instruction.volume);
+    }
+    Ok(())
+}
@@ -0,0 +1,232 @@
+/// A stack of crate labels, from the bottom crate up to the top crate at the end
+pub type Stack = Vec<String>;
+
+/// The top crate of each stack, left to right, concatenated as in the puzzle answer.
+/// Empty stacks contribute nothing.
+pub fn top_crates(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last())
+        .map(String::as_str)
+        .collect()
+}
+
+/// A run of non-space characters in a diagram line, with the char columns it spans
+struct Token<'a> {
+    text: &'a str,
+    first: usize,
+    last: usize,
+}
+
+impl Token<'_> {
+    /// Twice the centre column, to stay in whole numbers
+    fn centre2(&self) -> usize {
+        self.first + self.last
+    }
+}
+
+/// Split a line into whitespace separated tokens, keeping their char positions
+fn tokens(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start: Option<(usize, usize)> = None; // (byte offset, char column)
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+
+    for (column, &(offset, c)) in chars.iter().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((offset, column)),
+            (Some((from, first)), true) => {
+                tokens.push(Token {
+                    text: &line[from..offset],
+                    first,
+                    last: column - 1,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((from, first)) = start {
+        tokens.push(Token {
+            text: &line[from..],
+            first,
+            last: chars.len() - 1,
+        });
+    }
+    tokens
+}
+
+/// Split a crate row into bracketed crates like "[Z]", keeping their char positions.
+/// Anything other than whitespace between crates is an error.
+fn crate_tokens(line: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = vec![];
+    let mut open: Option<(usize, usize)> = None; // (byte offset, char column) of '['
+
+    for (column, (offset, c)) in line.char_indices().enumerate() {
+        match (open, c) {
+            (None, '[') => open = Some((offset, column)),
+            (None, c) if c.is_whitespace() => {}
+            (Some((from, first)), ']') => {
+                tokens.push(Token {
+                    text: &line[from + 1..offset],
+                    first,
+                    last: column,
+                });
+                open = None;
+            }
+            (Some(_), '[') | (None, _) => {
+                return Err(format!("unexpected '{c}' at column {}", column + 1))
+            }
+            (Some(_), _) => {}
+        }
+    }
+    match open {
+        Some((_, first)) => Err(format!("crate at column {} is not closed", first + 1)),
+        None => Ok(tokens),
+    }
+}
+
+/// Parse the crate drawing, e.g.
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// The number of stacks comes from the numbered label row, which must count up from 1.
+/// Each crate belongs to the stack whose label is closest to its centre, so rows may be
+/// ragged or trimmed, and crate labels may be several characters wide (e.g. "[AB]").
+pub fn parse(drawing: &str) -> Result<Vec<Stack>, String> {
+    let mut lines: Vec<&str> = drawing.lines().filter(|l| !l.trim().is_empty()).collect();
+    let label_row = lines
+        .pop()
+        .ok_or_else(|| "the drawing has no stack label row".to_string())?;
+
+    let labels = tokens(label_row);
+    for (i, label) in labels.iter().enumerate() {
+        if label.text.parse::<usize>() != Ok(i + 1) {
+            return Err(format!(
+                "stack label '{}' should be {} (labels count up from 1)",
+                label.text,
+                i + 1
+            ));
+        }
+    }
+
+    let mut stacks: Vec<Stack> = vec![Stack::new(); labels.len()];
+    // Read rows bottom up, so each crate is pushed on top of the ones below it
+    for (row, line) in lines.iter().enumerate().rev() {
+        let mut filled = vec![false; labels.len()];
+        for token in crate_tokens(line).map_err(|e| format!("row {}: {e}", row + 1))? {
+            let name = token.text.trim();
+            if name.is_empty() {
+                return Err(format!(
+                    "row {}: crate at column {} has no label",
+                    row + 1,
+                    token.first + 1
+                ));
+            }
+
+            let stack = (0..labels.len())
+                .min_by_key(|&i| labels[i].centre2().abs_diff(token.centre2()))
+                .ok_or_else(|| format!("row {}: crate [{name}] has no stack", row + 1))?;
+            if filled[stack] {
+                return Err(format!(
+                    "row {}: more than one crate above stack {}",
+                    row + 1,
+                    stack + 1
+                ));
+            }
+            filled[stack] = true;
+            stacks[stack].push(name.to_string());
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// Draw stacks in the same format `parse` reads, with the numbered label row at the
+/// bottom. Every column is as wide as the widest crate, and crate rows are padded to
+/// the full width of the drawing, so a parsed drawing renders back exactly as it was.
+pub fn render(stacks: &[Stack]) -> String {
+    let label_width = stacks.len().to_string().len();
+    let crate_width = stacks
+        .iter()
+        .flatten()
+        .map(|name| name.chars().count() + 2)
+        .max()
+        .unwrap_or(3);
+    let width = crate_width.max(label_width);
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut rows: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(name) => format!("{:^width$}", format!("[{name}]")),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+
+    let labels = (1..=stacks.len())
+        .map(|i| format!("{i:^width$}"))
+        .collect::<Vec<String>>()
+        .join(" ");
+    rows.push(labels);
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render the stacks of a drawing and check they parse back the same
+    fn round_trip(drawing: &str) -> Vec<Stack> {
+        let stacks = parse(drawing).unwrap();
+        assert_eq!(parse(&render(&stacks)).unwrap(), stacks);
+        stacks
+    }
+
+    #[test]
+    fn bundled_drawing_renders_exactly() {
+        let (drawing, _) = include_str!("day5.txt").split_once("\n\n").unwrap();
+        round_trip(drawing);
+        assert_eq!(render(&parse(drawing).unwrap()), drawing);
+    }
+
+    #[test]
+    fn ragged_drawing() {
+        let stacks = round_trip("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+        assert_eq!(stacks, [vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]]);
+        assert_eq!(
+            render(&stacks),
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+    }
+
+    #[test]
+    fn more_than_nine_stacks() {
+        let stacks: Vec<Stack> = (0..12)
+            .map(|i| {
+                (0..i % 4)
+                    .map(|h| format!("{}", (b'A' + (i + h) as u8) as char))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(parse(&render(&stacks)).unwrap(), stacks);
+        assert!(render(&stacks).ends_with(" 10  11  12 "));
+    }
+
+    #[test]
+    fn wide_labels() {
+        let stacks = round_trip("    [XY]\n[A] [BC]    [D]\n 1   2   3   4");
+        assert_eq!(stacks[1], ["BC", "XY"]);
+        assert!(stacks[2].is_empty());
+    }
+}
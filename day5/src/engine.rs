@@ -0,0 +1,120 @@
+use crate::crane::{self, Crane};
+use crate::diagram::Stack;
+use crate::instruction::Instruction;
+
+/// A move that has been carried out, with the crates it lifted so it can be taken back
+struct Step {
+    instruction: Instruction,
+    /// The top crates of the source stack before the move, bottom first
+    lifted: Vec<String>,
+}
+
+/// Carries out a log of instructions one at a time with a given crane, keeping enough
+/// history to undo and redo moves, or to jump to the state after any instruction.
+///
+/// The log is the history: undoing moves back through it, and performing a new move
+/// after undoing replaces the rest of the log, as in a text editor.
+pub struct Engine<'a> {
+    crane: &'a dyn Crane,
+    stacks: Vec<Stack>,
+    log: Vec<Instruction>,
+    done: Vec<Step>,
+}
+
+impl<'a> Engine<'a> {
+    /// An engine at the start of 'log', which is validated as each move is made
+    pub fn new(crane: &'a dyn Crane, stacks: Vec<Stack>, log: Vec<Instruction>) -> Engine<'a> {
+        Engine {
+            crane,
+            stacks,
+            log,
+            done: vec![],
+        }
+    }
+
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    pub fn log(&self) -> &[Instruction] {
+        &self.log
+    }
+
+    /// How many instructions of the log have been carried out
+    pub fn position(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Carry out 'instruction' as the next move, dropping any undone moves after it
+    pub fn perform(&mut self, instruction: Instruction) -> Result<(), String> {
+        let position = self.position();
+        self.carry_out(instruction)
+            .map_err(|e| format!("instruction {} ({instruction}): {e}", position + 1))?;
+        self.log.truncate(position);
+        self.log.push(instruction);
+        Ok(())
+    }
+
+    /// Take back the last move, returning it, or None at the start of the log
+    pub fn undo(&mut self) -> Option<Instruction> {
+        let Step {
+            instruction,
+            lifted,
+        } = self.done.pop()?;
+        if instruction.source != instruction.destination {
+            let destination = &mut self.stacks[instruction.destination - 1];
+            destination.truncate(destination.len() - instruction.volume);
+            self.stacks[instruction.source - 1].extend(lifted);
+        }
+        Some(instruction)
+    }
+
+    /// Carry out the next instruction of the log again, or None at the end of the log
+    pub fn redo(&mut self) -> Option<Result<Instruction, String>> {
+        let position = self.position();
+        let instruction = *self.log.get(position)?;
+        Some(
+            self.carry_out(instruction)
+                .map(|_| instruction)
+                .map_err(|e| format!("instruction {} ({instruction}): {e}", position + 1)),
+        )
+    }
+
+    /// Undo or redo moves until the first 'index' instructions have been carried out
+    pub fn seek(&mut self, index: usize) -> Result<(), String> {
+        if index > self.log.len() {
+            return Err(format!(
+                "cannot go to instruction {index}, the log has {} instructions",
+                self.log.len()
+            ));
+        }
+        while self.position() > index {
+            self.undo();
+        }
+        while self.position() < index {
+            if let Some(Err(e)) = self.redo() {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn carry_out(&mut self, instruction: Instruction) -> Result<(), String> {
+        instruction.validate(&self.stacks)?;
+        let source = &self.stacks[instruction.source - 1];
+        let lifted = source[source.len() - instruction.volume..].to_vec();
+        if instruction.source != instruction.destination {
+            let (source, destination) = crane::two_stacks(
+                &mut self.stacks,
+                instruction.source - 1,
+                instruction.destination - 1,
+            );
+            self.crane.lift(source, destination, instruction.volume);
+        }
+        self.done.push(Step {
+            instruction,
+            lifted,
+        });
+        Ok(())
+    }
+}
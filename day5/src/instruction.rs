@@ -0,0 +1,71 @@
+use std::fmt;
+
+use crate::diagram::Stack;
+
+/// One step of the rearrangement procedure, e.g. "move 11 from 3 to 9".
+/// Stack numbers are 1-based, as in the drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub volume: usize,
+    pub source: usize,
+    pub destination: usize,
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Result<Instruction, String> {
+        let number = |v: &str| {
+            v.parse::<usize>()
+                .map_err(|_| format!("'{v}' is not a number"))
+        };
+        match line.split_whitespace().collect::<Vec<&str>>()[..] {
+            // Destructure each instruction into volume, source stack, and destination stack
+            ["move", volume, "from", source, "to", destination] => Ok(Instruction {
+                volume: number(volume)?,
+                source: number(source)?,
+                destination: number(destination)?,
+            }),
+            _ => Err(format!("'{line}' is not of the form 'move N from A to B'")),
+        }
+    }
+
+    /// Check that both stacks exist and the source holds enough crates for the move
+    pub fn validate(&self, stacks: &[Stack]) -> Result<(), String> {
+        for stack in [self.source, self.destination] {
+            if stack == 0 || stack > stacks.len() {
+                return Err(format!(
+                    "there is no stack {stack} (stacks are numbered 1 to {})",
+                    stacks.len()
+                ));
+            }
+        }
+        let available = stacks[self.source - 1].len();
+        if self.volume > available {
+            return Err(format!(
+                "cannot move {} crates from stack {}, which holds {available}",
+                self.volume, self.source
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.volume, self.source, self.destination
+        )
+    }
+}
+
+/// Parse every line of the procedure, reporting the first bad line by number
+pub fn parse_all(procedure: &str) -> Result<Vec<Instruction>, String> {
+    procedure
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            Instruction::parse(line).map_err(|e| format!("instruction {}: {e}", i + 1))
+        })
+        .collect()
+}
@@ -0,0 +1,5 @@
+pub mod crane;
+pub mod diagram;
+pub mod engine;
+pub mod instruction;
+pub mod planner;
@@ -1,139 +1,149 @@
-use std::collections::VecDeque;
-
-/// Transpose the 2D vec of chars
-fn transpose(rows: Vec<VecDeque<char>>) -> Vec<VecDeque<char>> {
-    let height = rows.len();
-    let width = rows.first().unwrap().len();
-    let mut transposed: Vec<VecDeque<char>> =
-        vec![VecDeque::from(vec![' '; height as usize]); width as usize];
-    for i in 0..height {
-        for j in 0..width {
-            transposed[j][i] = rows[i][j]
+use day5::crane::{self, Crane, CrateMover9000, CrateMover9001};
+use day5::diagram::{self, Stack};
+use day5::engine::Engine;
+use day5::instruction::{self, Instruction};
+use day5::planner;
+
+/// How many stack states the planner may visit before giving up
+const PLAN_STATE_LIMIT: usize = 2_000_000;
+
+/// Move the crates with 'crane', stopping after the first 'at' instructions if given
+fn run(
+    crane: &dyn Crane,
+    stacks: &[Stack],
+    instructions: &[Instruction],
+    at: Option<usize>,
+) -> Result<Vec<Stack>, String> {
+    match at {
+        None => {
+            let mut moved = stacks.to_vec();
+            crane::apply(crane, &mut moved, instructions)?;
+            Ok(moved)
+        }
+        Some(index) => {
+            let mut engine = Engine::new(crane, stacks.to_vec(), instructions.to_vec());
+            engine.seek(index)?;
+            Ok(engine.stacks().to_vec())
         }
     }
-    transposed
 }
 
-/// Given a vector of crate stacks (VecDeque(s)), return the top crate for each stack in a concat string format
-fn get_top_crates(stacks: Vec<VecDeque<char>>) -> String {
-    let top_crates = String::from_iter(
-        stacks
-            .iter()
-            .map(|stack| stack.front().unwrap().to_owned())
-            .collect::<Vec<char>>(),
-    );
-
-    top_crates
+/// Command line options:
+/// `day5 [--crane 9000|9001|9001:N]... [--at N] [--render] [--plan TOPS] [--input PATH]`
+struct Options {
+    cranes: Vec<Box<dyn Crane>>,
+    /// Search for the fewest moves that leave these crates on top, instead of following
+    /// the procedure
+    plan: Option<String>,
+    /// Only carry out the first N instructions
+    at: Option<usize>,
+    /// Draw the final stacks after each crane, as well as the top crates
+    render: bool,
+    input: String,
 }
 
-fn main() {
-    // Split input into cargo and instructions sections
-    let (cargo, instructions) = include_str!("day5.txt").split_once("\n\n").unwrap();
-
-    println!("{}", cargo);
-
-    // Split cargo section of input into 2D vector of cargo labels
-    let rows: Vec<VecDeque<char>> = cargo
-        .lines()
-        .map(|line| {
-            // Parse into rows of chars
-            let mut row: VecDeque<char> = VecDeque::from(vec![' '; 9 as usize]);
-            let mut counter = 0;
-            for (i, label) in line.chars().enumerate() {
-                if i % 4 == 1 {
-                    // Labels occur every 4 characters, starting at char index 1
-                    row[counter] = label;
-                    counter += 1;
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            cranes: vec![],
+            plan: None,
+            at: None,
+            render: false,
+            input: include_str!("day5.txt").to_string(),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--crane" => options.cranes.push(crane::by_name(&value()?)?),
+                "--at" => {
+                    let at = value()?;
+                    options.at = Some(
+                        at.parse()
+                            .map_err(|_| format!("'{at}' is not an instruction count"))?,
+                    );
+                }
+                "--plan" => options.plan = Some(value()?),
+                "--render" => options.render = true,
+                "--input" => {
+                    let path = value()?;
+                    options.input = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("could not read {path}: {e}"))?;
                 }
+                _ => return Err(format!("unknown argument '{arg}'")),
             }
-            row
-        })
-        .collect();
-
-    // Exclude the final row, which contains only the stack label (1 to 9)
-    let crate_rows = rows.split_last().unwrap().1.to_owned();
-
-    // Transpose the rows, so each inner vector represents a cargo stack of crate labels
-    let mut stacks = transpose(crate_rows);
-
-    // Pop chars with values ' ', as they are not required
-    for stack in &mut stacks {
-        while stack.front().unwrap().clone() == ' ' {
-            stack.pop_front();
         }
+
+        Ok(options)
     }
-    // println!("{:#?}", stacks);
+}
 
-    // Save this format of stacks for part 2
-    let orginal_stacks = stacks.clone();
+fn main() -> Result<(), String> {
+    let Options {
+        cranes,
+        plan,
+        at,
+        render,
+        input,
+    } = Options::from_args(std::env::args().skip(1))?;
 
-    // Parse instructions strings into integer values
-    // Instruction format: move 11 from 3 to 9
-    let parsed_instructions: Vec<(usize, usize, usize)> = instructions
-        .lines()
-        .map(|instruction| instruction.split(" ").collect::<Vec<&str>>())
-        .map(|instruction| match instruction[..] {
-            // Destructure each instruction into tuple of volume, source stack, and destination stack
-            [_, volume, _, source, _, destination] => (
-                volume.parse::<usize>().unwrap(),
-                source.parse::<usize>().unwrap(),
-                destination.parse::<usize>().unwrap(),
-            ),
-            _ => todo!("We guarentee no other format."),
-        })
-        .collect::<Vec<(usize, usize, usize)>>();
-
-    // println!("{:#?}", parsed_instructions);
-
-    // Apply instructions to cargo stacks
-    // Use cloned parsed_instructions due to applied consumption of the values (that we need for part 2)
-    for (volume, source, destination) in parsed_instructions.clone() {
-        let mut source_stack = stacks.get_mut(source - 1).unwrap().clone();
-        let mut destination_stack = stacks.get_mut(destination - 1).unwrap().clone();
-        // Move crates one by one, from source stack to destination stack
-        for _ in 0..volume {
-            let c = source_stack.pop_front().unwrap();
-            destination_stack.push_front(c);
+    // Split input into cargo and instructions sections
+    let (cargo, instructions) = input.split_once("\n\n").ok_or_else(|| {
+        "expected a drawing and a procedure separated by a blank line".to_string()
+    })?;
+
+    // Parse the cargo section into stacks of crate labels, with the top crate at the end
+    let stacks = diagram::parse(cargo)?;
+
+    println!("{}", diagram::render(&stacks));
+
+    // Print the planned moves after the drawing, so the output is itself a puzzle input
+    if let Some(target) = plan {
+        let crane: Box<dyn Crane> = match cranes.len() {
+            0 => Box::new(CrateMover9000),
+            1 => cranes.into_iter().next().unwrap(),
+            _ => return Err("--plan takes at most one --crane".to_string()),
+        };
+        let moves = planner::plan(crane.as_ref(), &stacks, &target, PLAN_STATE_LIMIT)?;
+        println!();
+        for instruction in moves {
+            println!("{instruction}");
         }
-        // Replace original source and destination stacks with their modified versions
-        stacks[source - 1] = source_stack.to_owned();
-        stacks[destination - 1] = destination_stack.to_owned();
+        return Ok(());
     }
 
-    // println!("{:#?}", stacks);
-
-    // Get top crates for Part 1
-    let top_crates = get_top_crates(stacks);
+    // Instruction format: move 11 from 3 to 9
+    let instructions = instruction::parse_all(instructions)?;
 
-    println!("Part 1: {}", top_crates);
+    if cranes.is_empty() {
+        // Part 1: Crates are moved one at a time
+        let stacks1 = run(&CrateMover9000, &stacks, &instructions, at)?;
 
-    // ----------------------------------------------------------------------
+        if render {
+            println!("{}", diagram::render(&stacks1));
+        }
+        println!("Part 1: {}", diagram::top_crates(&stacks1));
 
-    // Part 2: Retain order of crates moved within a single instruction
-    // Apply instructions to cargo 'orginal_stacks'
-    // Use orginal_stacks, as the unmodified original parse stacks
-    let mut stacks2 = orginal_stacks;
+        // Part 2: Retain order of crates moved within a single instruction
+        let stacks2 = run(&CrateMover9001, &stacks, &instructions, at)?;
 
-    for (volume, source, destination) in parsed_instructions {
-        let mut source_stack = stacks2.get_mut(source - 1).unwrap().clone();
-        let mut destination_stack = stacks2.get_mut(destination - 1).unwrap().clone();
-        // Staging deque used to pull the top 'volume' crates off the source stack
-        // and store them until we push each crate back onto the destination stack
-        let mut staging_deque: VecDeque<char> = VecDeque::new();
-        // Pop crates off of source stack, and store in staging area
-        for _ in 0..volume {
-            let c = source_stack.pop_front().unwrap();
-            staging_deque.push_front(c);
+        if render {
+            println!("{}", diagram::render(&stacks2));
         }
-        // Push staging area crates onto destination stack
-        for _ in 0..volume {
-            let c = staging_deque.pop_front().unwrap();
-            destination_stack.push_front(c);
+        println!("Part 2: {}", diagram::top_crates(&stacks2));
+    } else {
+        for crane in cranes {
+            let moved = run(crane.as_ref(), &stacks, &instructions, at)?;
+
+            if render {
+                println!("{}", diagram::render(&moved));
+            }
+            println!("{}: {}", crane.name(), diagram::top_crates(&moved));
         }
-        // Replace original source and destination stacks with their modified versions
-        stacks2[source - 1] = source_stack.to_owned();
-        stacks2[destination - 1] = destination_stack.to_owned();
     }
 
-    println!("Part 2: {}", get_top_crates(stacks2));
+    Ok(())
 }
@@ -0,0 +1,167 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::crane::{self, Crane};
+use crate::diagram::{self, Stack};
+use crate::instruction::Instruction;
+
+/// Stack states packed as crate ids, with a separator after each stack, so the search
+/// can keep millions of them
+struct Codec {
+    labels: Vec<String>,
+}
+
+const SEPARATOR: u16 = u16::MAX;
+
+impl Codec {
+    fn new(stacks: &[Stack]) -> Result<Codec, String> {
+        let mut labels: Vec<String> = stacks.iter().flatten().cloned().collect();
+        labels.sort();
+        labels.dedup();
+        if labels.len() >= SEPARATOR as usize {
+            return Err(format!(
+                "too many different crates to plan ({})",
+                labels.len()
+            ));
+        }
+        Ok(Codec { labels })
+    }
+
+    fn encode(&self, stacks: &[Stack]) -> Box<[u16]> {
+        let mut packed = vec![];
+        for stack in stacks {
+            for name in stack {
+                packed.push(self.labels.binary_search(name).unwrap() as u16);
+            }
+            packed.push(SEPARATOR);
+        }
+        packed.into_boxed_slice()
+    }
+
+    fn decode(&self, packed: &[u16]) -> Vec<Stack> {
+        let mut stacks = vec![Stack::new()];
+        for &id in packed {
+            match id {
+                SEPARATOR => stacks.push(Stack::new()),
+                _ => stacks
+                    .last_mut()
+                    .unwrap()
+                    .push(self.labels[id as usize].clone()),
+            }
+        }
+        stacks.pop();
+        stacks
+    }
+}
+
+/// When the target names one single-letter crate for every stack, each stack's top crate
+/// is known, which gives the search a lower bound on the moves still needed
+fn per_stack_target(stacks: &[Stack], target: &str) -> Option<Vec<String>> {
+    let single_letters = stacks
+        .iter()
+        .flatten()
+        .all(|name| name.chars().count() == 1);
+    let target: Vec<String> = target.chars().map(String::from).collect();
+    (single_letters && target.len() == stacks.len()).then_some(target)
+}
+
+/// A lower bound on the moves left: a move changes the top crate of at most two stacks
+fn estimate(stacks: &[Stack], target: &Option<Vec<String>>) -> usize {
+    match target {
+        Some(target) => {
+            let wrong = stacks
+                .iter()
+                .zip(target)
+                .filter(|(stack, top)| stack.last() != Some(top))
+                .count();
+            wrong.div_ceil(2)
+        }
+        None => 0,
+    }
+}
+
+/// Find a shortest sequence of moves with 'crane' after which the top crates spell
+/// 'target', searching stack states with A*. Gives up after visiting 'max_states' states.
+pub fn plan(
+    crane: &dyn Crane,
+    stacks: &[Stack],
+    target: &str,
+    max_states: usize,
+) -> Result<Vec<Instruction>, String> {
+    let per_stack = per_stack_target(stacks, target);
+    if let Some(tops) = &per_stack {
+        if let Some(missing) = tops
+            .iter()
+            .find(|top| !stacks.iter().flatten().any(|n| n == *top))
+        {
+            return Err(format!("there is no crate [{missing}] to put on top"));
+        }
+    }
+
+    // Every state seen so far, with the fewest moves found to it and how it was reached
+    let codec = Codec::new(stacks)?;
+    let start = codec.encode(stacks);
+    let mut states: Vec<Box<[u16]>> = vec![start.clone()];
+    let mut index: HashMap<Box<[u16]>, usize> = HashMap::from([(start, 0)]);
+    let mut moves: Vec<usize> = vec![0];
+    let mut reached_by: Vec<Option<(usize, Instruction)>> = vec![None];
+
+    let mut queue = BinaryHeap::from([Reverse((estimate(stacks, &per_stack), 0, 0))]);
+    while let Some(Reverse((_, taken, id))) = queue.pop() {
+        if taken > moves[id] {
+            continue;
+        }
+        let state = codec.decode(&states[id]);
+        if diagram::top_crates(&state) == target {
+            let mut path = vec![];
+            let mut at = id;
+            while let Some((previous, instruction)) = reached_by[at] {
+                path.push(instruction);
+                at = previous;
+            }
+            path.reverse();
+            return Ok(path);
+        }
+        if states.len() > max_states {
+            return Err(format!(
+                "gave up after {max_states} states without reaching '{target}'"
+            ));
+        }
+
+        for source in 0..state.len() {
+            for destination in (0..state.len()).filter(|&d| d != source) {
+                for volume in 1..=state[source].len() {
+                    let mut next = state.clone();
+                    let (from, to) = crane::two_stacks(&mut next, source, destination);
+                    crane.lift(from, to, volume);
+                    let bound = taken + 1 + estimate(&next, &per_stack);
+                    let next = codec.encode(&next);
+
+                    let next_id = match index.get(&next) {
+                        Some(&seen) if moves[seen] <= taken + 1 => continue,
+                        Some(&seen) => seen,
+                        None => {
+                            states.push(next.clone());
+                            moves.push(usize::MAX);
+                            reached_by.push(None);
+                            index.insert(next, states.len() - 1);
+                            states.len() - 1
+                        }
+                    };
+                    moves[next_id] = taken + 1;
+                    reached_by[next_id] = Some((
+                        id,
+                        Instruction {
+                            volume,
+                            source: source + 1,
+                            destination: destination + 1,
+                        },
+                    ));
+                    queue.push(Reverse((bound, taken + 1, next_id)));
+                }
+            }
+        }
+    }
+
+    Err(format!("no sequence of moves puts '{target}' on top"))
+}
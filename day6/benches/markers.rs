@@ -0,0 +1,98 @@
+//! Compares the sliding count table against the previous approach, which built a
+//! `HashSet` from the whole window for every character.
+//!
+//! Run with `cargo bench`.
+
+use std::collections::{HashSet, VecDeque};
+use std::hint::black_box;
+use std::str::Chars;
+use std::time::{Duration, Instant};
+
+use day6::marker::find_start_of_packet_marker;
+use day6::stream::detect;
+
+const STREAM_LENGTH: usize = 1_000_000;
+const WINDOW_SIZES: [usize; 3] = [4, 14, 90];
+
+/// The previous implementation, which returned 0 when there was no marker
+fn find_with_hash_set(datastream: Chars, window_size: usize) -> usize {
+    let mut window: VecDeque<char> = VecDeque::new();
+
+    for (i, c) in datastream.enumerate() {
+        window.push_front(c);
+        if window.len() > window_size {
+            window.pop_back();
+        }
+        let set: HashSet<char> = HashSet::from_iter(window.iter().cloned());
+        if set.len() == window_size {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// A long stream cycling through 'repeating' symbols, then a run of 'window_size'
+/// different ones. With fewer repeating symbols than the window, no marker turns up until
+/// the very end, so every version scans all of it.
+fn stream(repeating: usize, window_size: usize) -> String {
+    let symbols: Vec<char> = ('!'..='~').take(window_size).collect();
+    assert_eq!(symbols.len(), window_size, "not enough printable symbols");
+
+    let mut stream: String = symbols[..repeating]
+        .iter()
+        .cycle()
+        .take(STREAM_LENGTH)
+        .collect();
+    stream.extend(symbols.iter().rev());
+    stream
+}
+
+fn time<T, F: FnMut() -> T>(mut f: F) -> (Duration, T) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (start.elapsed(), result)
+}
+
+fn main() {
+    println!("{STREAM_LENGTH} character streams");
+
+    for window_size in WINDOW_SIZES {
+        let stream = stream(window_size - 1, window_size);
+        let (hash_set, expected) = time(|| find_with_hash_set(stream.chars(), window_size));
+        let (counts, actual) = time(|| find_start_of_packet_marker(stream.as_bytes(), window_size));
+        assert_eq!(
+            expected,
+            actual.unwrap_or(0),
+            "window of {window_size} gave a different marker"
+        );
+
+        println!(
+            "window {:>3}  hash set: {:>10.2?}  count table: {:>10.2?}  ({:.0}x faster)",
+            window_size,
+            hash_set,
+            counts,
+            hash_set.as_secs_f64() / counts.as_secs_f64()
+        );
+    }
+
+    // Every size at once, on a stream where even the smallest window is found last
+    let stream = stream(WINDOW_SIZES[0] - 1, WINDOW_SIZES[2]);
+    let (separate, expected) = time(|| {
+        WINDOW_SIZES
+            .map(|size| find_start_of_packet_marker(stream.as_bytes(), size))
+            .to_vec()
+    });
+    let (one_pass, actual) = time(|| {
+        let mut found = [None; 3];
+        detect(stream.as_bytes(), &WINDOW_SIZES, |marker| {
+            let i = WINDOW_SIZES.iter().position(|&s| s == marker.window_size);
+            found[i.unwrap()] = Some(marker.offset as usize);
+        })
+        .unwrap();
+        found.to_vec()
+    });
+    assert_eq!(expected, actual, "one pass gave different markers");
+    println!(
+        "windows {WINDOW_SIZES:?}  separately: {separate:>10.2?}  one pass: {one_pass:>10.2?}"
+    );
+}
@@ -0,0 +1,87 @@
+use std::ops::Range;
+
+use crate::marker::{Window, MESSAGE_WINDOW, PACKET_WINDOW};
+
+/// The two kinds of marker that frame a datastream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Packet,
+    Message,
+}
+
+impl Kind {
+    /// How many different bytes in a row make the marker
+    pub fn window_size(self) -> usize {
+        match self {
+            Kind::Packet => PACKET_WINDOW,
+            Kind::Message => MESSAGE_WINDOW,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Packet => "packet",
+            Kind::Message => "message",
+        }
+    }
+}
+
+/// A marker and the payload that follows it, up to the next marker or the end of the
+/// stream. Ranges are byte offsets into the datastream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub kind: Kind,
+    pub marker: Range<usize>,
+    pub payload: Range<usize>,
+}
+
+/// Markers in 'datastream[range]' that do not overlap: the window starts afresh after
+/// each one, so the next marker is made only of bytes after it
+fn markers(datastream: &[u8], range: Range<usize>, window_size: usize) -> Vec<Range<usize>> {
+    let mut markers = vec![];
+    let mut window = Window::new(window_size);
+    for offset in range {
+        if window.push(datastream[offset]) {
+            markers.push(offset + 1 - window_size..offset + 1);
+            window = Window::new(window_size);
+        }
+    }
+    markers
+}
+
+/// Split the whole datastream into segments at every marker. Start-of-message markers are
+/// found first, and start-of-packet markers are then looked for only in the bytes between
+/// them, so no two markers share a byte. Bytes before the first marker belong to no
+/// segment.
+pub fn decode(datastream: &[u8]) -> Vec<Segment> {
+    let messages = markers(datastream, 0..datastream.len(), Kind::Message.window_size());
+
+    let mut found: Vec<(Kind, Range<usize>)> = vec![];
+    let mut gap_start = 0;
+    for marker in messages.into_iter().map(Some).chain([None]) {
+        let gap_end = marker.as_ref().map_or(datastream.len(), |m| m.start);
+        for packet in markers(datastream, gap_start..gap_end, Kind::Packet.window_size()) {
+            found.push((Kind::Packet, packet));
+        }
+        if let Some(marker) = marker {
+            gap_start = marker.end;
+            found.push((Kind::Message, marker));
+        }
+    }
+
+    let next_starts: Vec<usize> = found
+        .iter()
+        .skip(1)
+        .map(|(_, marker)| marker.start)
+        .chain([datastream.len()])
+        .collect();
+    found
+        .into_iter()
+        .zip(next_starts)
+        .map(|((kind, marker), next)| Segment {
+            kind,
+            payload: marker.end..next,
+            marker,
+        })
+        .collect()
+}
@@ -1,34 +1,158 @@
-use std::collections::{HashSet, VecDeque};
-use std::str::Chars;
+use std::fs::File;
+use std::io::{self, Read};
 
+use day6::decoder;
+use day6::marker::{MESSAGE_WINDOW, PACKET_WINDOW};
+use day6::stream;
 
-fn find_start_of_packet_marker(datastream: Chars, window_size: usize) -> usize {
-    let mut window: VecDeque<char> = VecDeque::new();
+enum Mode {
+    /// Print the first start-of-packet and start-of-message markers
+    Markers,
+    /// Split the whole datastream into packets and messages, after reading all of it
+    Decode,
+    /// Print the first marker for each of these window sizes, and the longest run of
+    /// different bytes
+    Windows(Vec<usize>),
+}
+
+/// Command line options: `day6 [decode | --window N...] [--input PATH | --stdin]`
+///
+/// Without 'decode', files and stdin are read a chunk at a time, so they can be larger
+/// than memory or an endless pipe; markers are printed as soon as they are found.
+/// 'decode' reads the whole input into memory before printing anything, so it needs a
+/// stream that ends.
+struct Options {
+    mode: Mode,
+    input: Box<dyn Read>,
+}
+
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            mode: Mode::Markers,
+            input: Box::new(include_str!("day6.txt").as_bytes()),
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--input" => {
+                    let path = value()?;
+                    options.input = Box::new(
+                        File::open(&path).map_err(|e| format!("could not open {path}: {e}"))?,
+                    );
+                }
+                "--stdin" => options.input = Box::new(io::stdin().lock()),
+                "decode" => options.mode = Mode::Decode,
+                "--window" => {
+                    let size = value()?;
+                    let size = size
+                        .parse()
+                        .map_err(|_| format!("'{size}' is not a window size"))?;
+                    match &mut options.mode {
+                        Mode::Windows(sizes) => sizes.push(size),
+                        mode => *mode = Mode::Windows(vec![size]),
+                    }
+                }
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Print each segment of the datastream on one line, with its payload escaped
+fn print_segments(datastream: &[u8]) {
+    let segments = decoder::decode(datastream);
+    let framed = segments
+        .first()
+        .map_or(datastream.len(), |s| s.marker.start);
+    println!("{framed} bytes before the first marker");
+
+    for segment in &segments {
+        println!(
+            "{:<7} marker {:>5}..{:<5} payload {:>5}..{:<5} {}",
+            segment.kind.name(),
+            segment.marker.start,
+            segment.marker.end,
+            segment.payload.start,
+            segment.payload.end,
+            datastream[segment.payload.clone()].escape_ascii()
+        );
+    }
+}
+
+/// Stream the datastream in one pass, printing the first marker for every window size as
+/// it is found, then the longest run of different bytes once the stream ends
+fn print_windows(input: impl Read, window_sizes: &[usize]) -> Result<(), String> {
+    let mut found = vec![];
+    let longest = stream::survey(input, window_sizes, |marker| {
+        println!("Window {}: {}", marker.window_size, marker.offset);
+        found.push(marker.window_size);
+    })
+    .map_err(|e| format!("could not read the datastream: {e}"))?;
 
-    for (i, c) in datastream.enumerate() {
-        window.push_front(c);
-        if window.len() > window_size {
-            window.pop_back();
+    for size in window_sizes {
+        if !found.contains(size) {
+            println!("Window {size}: no marker");
         }
-        let set: HashSet<char> = HashSet::from_iter(window.iter().cloned());
-        if set.len() == window_size {
-            return i+1;
+    }
+    println!(
+        "Longest run of different bytes: {} at {}..{}",
+        longest.end - longest.start,
+        longest.start,
+        longest.end
+    );
+
+    Ok(())
+}
+
+/// Stream the datastream, printing the part 1 and part 2 markers as they are found
+fn print_markers(input: impl Read) -> Result<(), String> {
+    let mut found = vec![];
+    stream::detect(input, &[PACKET_WINDOW, MESSAGE_WINDOW], |marker| {
+        let part = if marker.window_size == PACKET_WINDOW {
+            1
+        } else {
+            2
+        };
+        println!("Part {part}: {}", marker.offset);
+        found.push(marker.window_size);
+    })
+    .map_err(|e| format!("could not read the datastream: {e}"))?;
+
+    for (part, window_size, kind) in [
+        (1, PACKET_WINDOW, "start-of-packet"),
+        (2, MESSAGE_WINDOW, "start-of-message"),
+    ] {
+        if !found.contains(&window_size) {
+            println!("Part {part}: the datastream has no {kind} marker");
         }
     }
-    return 0
+
+    Ok(())
 }
 
+fn read_all(mut input: impl Read) -> Result<Vec<u8>, String> {
+    let mut datastream = vec![];
+    input
+        .read_to_end(&mut datastream)
+        .map_err(|e| format!("could not read the datastream: {e}"))?;
+    Ok(datastream)
+}
 
-fn main() {
-    
-    let datastream = include_str!("day6.txt").chars();
-    
-    // Part 1
-    let part1 = find_start_of_packet_marker(datastream.clone(), 4);
-    println!("Part 1: {}", part1);
+fn main() -> Result<(), String> {
+    let Options { mode, input } = Options::from_args(std::env::args().skip(1))?;
 
-    // Part 2
-    let part2 = find_start_of_packet_marker(datastream.clone(), 14);
-    println!("Part 2: {}", part2);
+    match mode {
+        Mode::Markers => print_markers(input)?,
+        Mode::Decode => print_segments(&read_all(input)?),
+        Mode::Windows(sizes) => print_windows(input, &sizes)?,
+    }
 
+    Ok(())
 }
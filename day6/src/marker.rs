@@ -0,0 +1,100 @@
+/// Window sizes of the start-of-packet (part 1) and start-of-message (part 2) markers
+pub const PACKET_WINDOW: usize = 4;
+pub const MESSAGE_WINDOW: usize = 14;
+
+/// The last 'size' bytes of a datastream, with a count of each byte value in it, so that
+/// sliding along one byte updates whether they are all different in constant time
+pub struct Window {
+    size: usize,
+    /// The bytes in the window, oldest at 'start', wrapping round
+    ring: Vec<u8>,
+    start: usize,
+    counts: [usize; 256],
+    /// How many byte values have a non-zero count
+    distinct: usize,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Window {
+        Window {
+            size,
+            ring: Vec::with_capacity(size),
+            start: 0,
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    /// Slide the window on by one byte, returning whether it now holds 'size' different bytes
+    pub fn push(&mut self, byte: u8) -> bool {
+        if self.size == 0 {
+            return true;
+        }
+        if self.ring.len() < self.size {
+            self.ring.push(byte);
+        } else {
+            let oldest = std::mem::replace(&mut self.ring[self.start], byte);
+            self.start += 1;
+            if self.start == self.size {
+                self.start = 0;
+            }
+            self.counts[oldest as usize] -= 1;
+            if self.counts[oldest as usize] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        if self.counts[byte as usize] == 0 {
+            self.distinct += 1;
+        }
+        self.counts[byte as usize] += 1;
+        self.distinct == self.size
+    }
+}
+
+/// The number of bytes read when the last 'window_size' bytes are first all different,
+/// or None if the datastream has no such marker. An empty window is there from the start.
+pub fn find_start_of_packet_marker(datastream: &[u8], window_size: usize) -> Option<usize> {
+    if window_size == 0 {
+        return Some(0);
+    }
+    let mut window = Window::new(window_size);
+    datastream
+        .iter()
+        .position(|&byte| window.push(byte))
+        .map(|i| i + 1)
+}
+
+/// Tracks how far back the run of all different bytes goes as a datastream is fed in,
+/// one byte at a time, by remembering where each byte value was last seen. A run of
+/// length n or more ending at a byte is a marker for every window size up to n.
+pub struct DistinctRun {
+    /// One past the offset where each byte value was last seen, or 0 if never
+    last_seen: [u64; 256],
+    start: u64,
+    offset: u64,
+}
+
+impl Default for DistinctRun {
+    fn default() -> DistinctRun {
+        DistinctRun {
+            last_seen: [0; 256],
+            start: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl DistinctRun {
+    /// Take the next byte, returning the length of the run of different bytes it ends
+    pub fn push(&mut self, byte: u8) -> u64 {
+        self.start = self.start.max(self.last_seen[byte as usize]);
+        self.offset += 1;
+        self.last_seen[byte as usize] = self.offset;
+        self.offset - self.start
+    }
+
+    /// How many bytes have been taken
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::{self, ErrorKind, Read};
+use std::ops::Range;
+
+use crate::marker::DistinctRun;
+
+/// How many bytes are read from the stream at a time
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The first point in a stream where the last 'window_size' bytes are all different
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker {
+    pub window_size: usize,
+    /// The number of bytes read when the marker completes
+    pub offset: u64,
+}
+
+/// Call 'each' with every byte of 'reader', read a chunk at a time, until it returns
+/// false or the stream ends
+fn for_each_byte<R: Read>(mut reader: R, mut each: impl FnMut(u8) -> bool) -> io::Result<()> {
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if !chunk[..read].iter().all(|&byte| each(byte)) {
+            return Ok(());
+        }
+    }
+}
+
+/// Finds the first marker for many window sizes at once from a single run of different
+/// bytes, so each byte costs the same however many sizes are asked for
+struct Markers {
+    /// Window sizes still without a marker, largest first
+    pending: Vec<usize>,
+    run: DistinctRun,
+    longest: Range<u64>,
+}
+
+impl Markers {
+    /// Reports an empty window at offset 0 straight away, as it needs no bytes
+    fn new(window_sizes: &[usize], report: &mut impl FnMut(Marker)) -> Markers {
+        let mut pending = window_sizes.to_vec();
+        pending.sort_unstable_by(|a, b| b.cmp(a));
+        pending.dedup();
+        while pending.last() == Some(&0) {
+            pending.pop();
+            report(Marker {
+                window_size: 0,
+                offset: 0,
+            });
+        }
+        Markers {
+            pending,
+            run: DistinctRun::default(),
+            longest: 0..0,
+        }
+    }
+
+    /// Take the next byte, reporting the windows it completes a first marker for.
+    /// A run reaches each length in turn, so smaller windows find their marker first.
+    fn push(&mut self, byte: u8, report: &mut impl FnMut(Marker)) {
+        let run = self.run.push(byte);
+        let offset = self.run.offset();
+        if run > self.longest.end - self.longest.start {
+            self.longest = offset - run..offset;
+        }
+        while let Some(&window_size) = self.pending.last() {
+            if window_size as u64 > run {
+                break;
+            }
+            self.pending.pop();
+            report(Marker {
+                window_size,
+                offset,
+            });
+        }
+    }
+}
+
+/// Read 'reader' a chunk at a time, calling 'report' as soon as the first marker for each
+/// window size is found, all in one pass. Stops once every window size has a marker, or
+/// at the end of the stream, and returns how many bytes it looked at. Window sizes that
+/// never get a marker are not reported, and an empty window is reported at offset 0
+/// without reading.
+pub fn detect<R: Read>(
+    reader: R,
+    window_sizes: &[usize],
+    mut report: impl FnMut(Marker),
+) -> io::Result<u64> {
+    let mut markers = Markers::new(window_sizes, &mut report);
+    if !markers.pending.is_empty() {
+        for_each_byte(reader, |byte| {
+            markers.push(byte, &mut report);
+            !markers.pending.is_empty()
+        })?;
+    }
+    Ok(markers.run.offset())
+}
+
+/// Like `detect`, but reads to the end of the stream and returns the longest stretch in
+/// which no byte repeats, as byte offsets (the earliest one wins a tie)
+pub fn survey<R: Read>(
+    reader: R,
+    window_sizes: &[usize],
+    mut report: impl FnMut(Marker),
+) -> io::Result<Range<u64>> {
+    let mut markers = Markers::new(window_sizes, &mut report);
+    for_each_byte(reader, |byte| {
+        markers.push(byte, &mut report);
+        true
+    })?;
+    Ok(markers.longest)
+}